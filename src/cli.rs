@@ -3,7 +3,7 @@
 //! It defines the structure and functions necessary to interpret user input.
 
 use crate::ENV_VARS;
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use std::sync::LazyLock;
 
 static LONG_VERSION: LazyLock<String> = LazyLock::new(|| -> String {
@@ -71,6 +71,31 @@ pub struct GenerateCmd {
     /// Sets a custom config file path
     #[arg(short, long, required = false)]
     pub config: Option<std::path::PathBuf>,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+/// What to do with the members of bound classes which couldn't be emitted
+#[derive(Args, Debug, Default)]
+pub struct ReportArgs {
+    /// Prints the rejected members, the missing classes causing them and the skipped variants
+    #[arg(long, required = false)]
+    pub report: bool,
+
+    /// Writes the rejected members, the missing classes causing them and the skipped variants as JSON
+    #[arg(long, required = false, value_name = "PATH")]
+    pub report_json: Option<std::path::PathBuf>,
+
+    /// Fails when members of bound classes are rejected
+    #[arg(long, required = false)]
+    pub deny_rejections: bool,
+}
+
+impl ReportArgs {
+    pub fn is_requested(&self) -> bool {
+        self.report || self.report_json.is_some() || self.deny_rejections
+    }
 }
 
 impl Cli {
@@ -19,9 +19,12 @@ fn default_period() -> String {
 fn default_comma() -> String {
     ",".to_string()
 }
+fn default_runtime() -> String {
+    "{ git = \"https://github.com/OxidizeMC/java-oxide\" }".to_string()
+}
 
 /// Configuration for what classes to bind/proxy
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct IncludeConfig {
     /// What java class(es) to match against. This takes the form of a glob pattern matching JNI paths.
@@ -53,6 +56,44 @@ pub struct IncludeConfig {
     /// Whether to generate Java proxies. Setting to 'proxy = true' will force 'bind = true'
     #[serde(default)]
     pub proxy: Option<bool>,
+
+    /// How overloaded Java methods are bound. Defaults to "mangle".
+    #[serde(default)]
+    pub overloads: Option<OverloadStyle>,
+
+    /// Whether `java.lang.String` parameters accept Rust strings, and String-returning methods get `_string()`
+    /// variants returning a Rust `String`. Defaults to true.
+    #[serde(default)]
+    pub rust_strings: Option<bool>,
+
+    /// Whether parameters typed as a primitive wrapper class (`java.lang.Integer`, ...) accept Rust primitives, and
+    /// such return values are unboxed into `Option<i32>` and friends. Defaults to false.
+    #[serde(default)]
+    pub boxed_primitives: Option<bool>,
+
+    /// Whether parameters typed as `java.util.Optional` (or `OptionalInt`, ...) accept Rust `Option`s, and methods
+    /// returning one get `_option()` variants returning a Rust `Option`. Defaults to false.
+    #[serde(default)]
+    pub optionals: Option<bool>,
+
+    /// Whether to generate `is_available()`, `<name>_is_available()` and `try_<name>()` companions, which report
+    /// classes and members missing from the running JVM instead of panicking. Defaults to false.
+    #[serde(default)]
+    pub fallible_lookups: Option<bool>,
+
+    /// Whether to generate unsafe `<name>_unchecked()` variants of methods, which skip the pending exception check
+    /// and return the raw value. Defaults to false.
+    #[serde(default)]
+    pub unchecked_variants: Option<bool>,
+
+    /// Whether to also bind the classes found in the inputs which the matched classes reference: superclasses,
+    /// interfaces, and the types of their bound members, then the classes those reference, and so on. The pulled in
+    /// classes share this rule's settings, except for proxies. Defaults to false.
+    #[serde(default)]
+    pub transitive: Option<bool>,
+    /// How many references away from the matched classes to bind classes, implies 'transitive = true'
+    #[serde(default)]
+    pub depth: Option<u32>,
 }
 impl IncludeConfig {
     pub fn check(&self) -> Result<(), Vec<&'static str>> {
@@ -75,12 +116,29 @@ impl IncludeConfig {
                 "'include.bind' must also be set to true if any 'include.bind-private-*' values are set to true",
             );
         }
+        if self.depth.is_some() && self.transitive == Some(false) {
+            errors.push("'include.depth' can't be set if 'include.transitive' is set to false");
+        }
+        if self.reach().is_some() && self.bind == Some(false) {
+            errors.push(
+                "'include.transitive' and 'include.depth' require 'include.bind' not to be false",
+            );
+        }
         if !errors.is_empty() {
             return Err(errors);
         }
         Ok(())
     }
 
+    /// How many references away from the matched classes to bind classes, if the rule is transitive.
+    pub fn reach(&self) -> Option<u32> {
+        match (self.transitive, self.depth) {
+            (Some(false), _) | (None, None) => None,
+            (_, Some(depth)) => Some(depth),
+            (Some(true), None) => Some(u32::MAX),
+        }
+    }
+
     pub fn matches_class(&self, class: &str) -> bool {
         let options: glob::MatchOptions = glob::MatchOptions {
             case_sensitive: true,
@@ -98,6 +156,19 @@ impl IncludeConfig {
     }
 }
 
+/// How overloaded Java methods are bound
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverloadStyle {
+    /// One method per overload, with the argument types appended to colliding names (`new_int`, `new_Object`)
+    #[default]
+    Mangle,
+    /// One method per Java name, taking an argument tuple that selects the overload at compile time
+    /// (`new(env, (1,))`, `new(env, ("text",))`). Arguments fitting several overloads need the Java parameter types
+    /// of the one to call (`new::<(String,), _>(env, (&string,))`)
+    Dispatch,
+}
+
 /// Configuration for Documentation URL patterns
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "kebab-case")]
@@ -231,6 +302,82 @@ impl DocConfig {
     }
 }
 
+/// Configuration for a bindings crate generated separately, whose classes are referenced instead of bound again
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExternConfig {
+    /// Name of the crate as seen from the generated bindings, such as "java_base"
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+
+    /// Path to the binding manifest written alongside the crate's bindings
+    pub manifest: PathBuf,
+}
+impl ExternConfig {
+    pub fn check(&self) -> Result<(), Vec<&'static str>> {
+        let mut errors: Vec<&'static str> = Vec::new();
+        if self.crate_name.is_empty()
+            || !self
+                .crate_name
+                .chars()
+                .all(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        {
+            errors.push("'extern.crate' must be a crate name, using underscores instead of dashes");
+        }
+        if self.manifest.as_os_str().is_empty() {
+            errors.push("'extern.manifest' cannot be an empty string");
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
+/// Configuration for a Cargo feature gating the bindings of a group of Java classes, see `sources.features`
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct FeatureConfig {
+    /// Name of the Cargo feature, such as "fabric-event-lifecycle"
+    pub name: String,
+
+    /// What java class(es) belong to the feature. This takes the form of a glob pattern matching JNI paths, such as
+    /// "net/fabricmc/fabric/api/event/lifecycle/**".
+    #[serde(rename = "match")]
+    pub matches: Vec<String>,
+}
+impl FeatureConfig {
+    pub fn check(&self) -> Result<(), Vec<&'static str>> {
+        let mut errors: Vec<&'static str> = Vec::new();
+        if self.name.is_empty() {
+            errors.push("'feature.name' cannot be an empty string");
+        }
+        if self.matches.is_empty() {
+            errors.push("'feature.match' must list at least one pattern");
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+
+    pub fn matches_class(&self, class: &str) -> bool {
+        let options: glob::MatchOptions = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+
+        self.matches.iter().any(|p: &String| -> bool {
+            let pattern: glob::Pattern =
+                glob::Pattern::new(p).unwrap_or_else(|e: glob::PatternError| -> glob::Pattern {
+                    panic!("Invalid glob pattern '{p}': {e}")
+                });
+            pattern.matches_with(class, options)
+        })
+    }
+}
+
 /// Configuration for what separators to use when generating Documentation URLs
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "kebab-case")]
@@ -308,10 +455,50 @@ impl Default for ProxyConfig {
 pub struct SourceConfig {
     /// A list of path(s) to JAR input(s)
     pub inputs: Vec<PathBuf>,
-    /// Where to place generated bindings
+    /// Where to place generated bindings: either a file receiving every binding, or a directory receiving a complete
+    /// crate with one file per Java package, see `crate`
     pub output: PathBuf,
+    /// Whether `output` is a crate directory rather than a single file. Defaults to whether `output` ends with a path
+    /// separator or is an existing directory.
+    #[serde(default, rename = "crate")]
+    pub crate_output: Option<bool>,
+    /// The `java-oxide` dependency written to the `Cargo.toml` of a generated crate, as a TOML value. Defaults to
+    /// the git repository, `{ git = "https://github.com/OxidizeMC/java-oxide" }`.
+    #[serde(default = "default_runtime")]
+    pub runtime: String,
+    /// Whether to wrap generated methods, field accessors and proxy native entry points in `tracing` spans, compiled
+    /// only under the `java-oxide-trace` feature of the output crate. Defaults to false.
+    #[serde(default)]
+    pub tracing: bool,
+    /// Whether to gate bindings behind one Cargo feature per top-level package (or `[[feature]]` group), except for
+    /// `java.lang` which every binding needs. Defaults to false.
+    #[serde(default)]
+    pub features: bool,
+    /// Whether to emit method and field bodies as calls to the macros of the generated `macros` module instead of
+    /// expanding them inline, for smaller output. Defaults to false.
+    #[serde(default)]
+    pub macros: bool,
+    /// Where to write a JSON manifest of every bound class and member, which other configs can reference through
+    /// `[[extern]]`. Defaults to none.
+    #[serde(default)]
+    pub manifest: Option<PathBuf>,
+    /// Line width of the generated code. Defaults to the fixed width of `prettyplease`, 89 columns; other widths are
+    /// applied by reformatting its output with `rustfmt`, which must then be installed.
+    #[serde(default, rename = "max-width")]
+    pub max_width: Option<usize>,
 }
 impl SourceConfig {
+    /// Whether `output` is a directory receiving a complete crate, rather than a single file.
+    pub fn is_crate_output(&self) -> bool {
+        self.crate_output.unwrap_or_else(|| {
+            self.output
+                .as_os_str()
+                .to_string_lossy()
+                .ends_with(std::path::is_separator)
+                || self.output.is_dir()
+        })
+    }
+
     pub fn check(&self) -> Result<(), Vec<&'static str>> {
         let mut errors: Vec<&'static str> = Vec::new();
         if self.inputs.is_empty() {
@@ -323,6 +510,12 @@ impl SourceConfig {
         if self.output.as_os_str().is_empty() {
             errors.push("'source.output' cannot be an empty string");
         }
+        if toml::from_str::<toml::Table>(&format!("java-oxide = {}", self.runtime)).is_err() {
+            errors.push("'source.runtime' must be a TOML value, such as '\"0.1\"' or '{ path = \"../java-oxide\" }'");
+        }
+        if self.max_width == Some(0) {
+            errors.push("'source.max-width' must be greater than 0");
+        }
         if !errors.is_empty() {
             return Err(errors);
         }
@@ -348,6 +541,17 @@ pub struct Config {
     /// List of configurations for what classes to bind/proxy
     #[serde(rename = "include")]
     pub rules: Vec<IncludeConfig>,
+
+    /// Optional list of Cargo features grouping Java classes, overriding the default of one feature per top-level
+    /// package
+    #[serde(default)]
+    #[serde(rename = "feature")]
+    pub features: Vec<FeatureConfig>,
+
+    /// Optional list of bindings crates whose classes are referenced instead of bound again
+    #[serde(default)]
+    #[serde(rename = "extern")]
+    pub externs: Vec<ExternConfig>,
 }
 
 impl Config {
@@ -363,10 +567,16 @@ impl Config {
         config.check();
 
         config.src.output = resolve_file(&config.src.output, dir)?;
+        if let Some(manifest) = &mut config.src.manifest {
+            *manifest = resolve_file(manifest, dir)?;
+        }
         if let Some(output) = &mut config.proxy.output {
             *output = resolve_file(output, dir)?;
         }
         config.src.inputs = expand_inputs(&config.src.inputs, dir)?;
+        for extern_ in &mut config.externs {
+            extern_.manifest = resolve_file(&extern_.manifest, dir)?;
+        }
 
         config.proxy.package = config.proxy.package.replace(".", "/");
         if let Some(docs) = &mut config.docs {
@@ -381,6 +591,11 @@ impl Config {
                 *class = class.replace(".", "/");
             }
         }
+        for feature in &mut config.features {
+            for class in &mut feature.matches {
+                *class = class.replace(".", "/");
+            }
+        }
 
         // dbg!(&config);
         Ok(config)
@@ -443,6 +658,16 @@ impl Config {
                 errors.extend(e);
             }
         }
+        for feature in &self.features {
+            if let Err(e) = feature.check() {
+                errors.extend(e);
+            }
+        }
+        for extern_ in &self.externs {
+            if let Err(e) = extern_.check() {
+                errors.extend(e);
+            }
+        }
 
         if !errors.is_empty() {
             panic!(
@@ -452,6 +677,22 @@ impl Config {
         }
     }
 
+    /// The Cargo feature gating the bindings of `class`, if `sources.features` is enabled.
+    pub fn feature_for(&self, class: &str) -> Option<String> {
+        let (package, _) = class.rsplit_once('/')?;
+        if !self.src.features || package == "java/lang" {
+            return None;
+        }
+        match self
+            .features
+            .iter()
+            .find(|f: &&FeatureConfig| f.matches_class(class))
+        {
+            Some(feature) => Some(feature.name.clone()),
+            None => package.split('/').next().map(str::to_owned),
+        }
+    }
+
     pub fn resolve_class(&self, class: &str) -> ClassConfig<'_> {
         // false > true > None
         let mut temp_config: TempClassConfig = TempClassConfig {
@@ -460,6 +701,12 @@ impl Config {
             bind_private_methods: None,
             bind_private_fields: None,
             proxy: None,
+            overloads: None,
+            rust_strings: None,
+            boxed_primitives: None,
+            optionals: None,
+            fallible_lookups: None,
+            unchecked_variants: None,
         };
 
         for rule in &self.rules {
@@ -491,6 +738,37 @@ impl Config {
                 if temp_config.proxy.is_none() || temp_config.proxy == Some(true) {
                     temp_config.proxy = rule.proxy;
                 }
+
+                if temp_config.rust_strings.is_none() || temp_config.rust_strings == Some(true) {
+                    temp_config.rust_strings = rule.rust_strings;
+                }
+
+                if temp_config.boxed_primitives.is_none()
+                    || temp_config.boxed_primitives == Some(true)
+                {
+                    temp_config.boxed_primitives = rule.boxed_primitives;
+                }
+
+                if temp_config.optionals.is_none() || temp_config.optionals == Some(true) {
+                    temp_config.optionals = rule.optionals;
+                }
+
+                if temp_config.fallible_lookups.is_none()
+                    || temp_config.fallible_lookups == Some(true)
+                {
+                    temp_config.fallible_lookups = rule.fallible_lookups;
+                }
+
+                if temp_config.unchecked_variants.is_none()
+                    || temp_config.unchecked_variants == Some(true)
+                {
+                    temp_config.unchecked_variants = rule.unchecked_variants;
+                }
+
+                // Later rules override earlier ones
+                if rule.overloads.is_some() {
+                    temp_config.overloads = rule.overloads;
+                }
             }
         }
 
@@ -510,6 +788,12 @@ impl Config {
             bind_private_methods: temp_config.bind_private_methods.unwrap_or_default(),
             bind_private_fields: temp_config.bind_private_fields.unwrap_or_default(),
             proxy: temp_config.proxy.unwrap_or_default(),
+            overloads: temp_config.overloads.unwrap_or_default(),
+            rust_strings: temp_config.rust_strings.unwrap_or(true),
+            boxed_primitives: temp_config.boxed_primitives.unwrap_or_default(),
+            optionals: temp_config.optionals.unwrap_or_default(),
+            fallible_lookups: temp_config.fallible_lookups.unwrap_or_default(),
+            unchecked_variants: temp_config.unchecked_variants.unwrap_or_default(),
             doc_pattern: None,
         };
 
@@ -532,6 +816,12 @@ pub struct ClassConfig<'a> {
     pub bind_private_methods: bool,
     pub bind_private_fields: bool,
     pub proxy: bool,
+    pub overloads: OverloadStyle,
+    pub rust_strings: bool,
+    pub boxed_primitives: bool,
+    pub optionals: bool,
+    pub fallible_lookups: bool,
+    pub unchecked_variants: bool,
     pub doc_pattern: Option<&'a DocConfig>,
 }
 
@@ -542,6 +832,12 @@ pub struct TempClassConfig {
     pub bind_private_methods: Option<bool>,
     pub bind_private_fields: Option<bool>,
     pub proxy: Option<bool>,
+    pub overloads: Option<OverloadStyle>,
+    pub rust_strings: Option<bool>,
+    pub boxed_primitives: Option<bool>,
+    pub optionals: Option<bool>,
+    pub fallible_lookups: Option<bool>,
+    pub unchecked_variants: Option<bool>,
 }
 
 fn resolve_file(path: &Path, dir: &Path) -> io::Result<PathBuf> {
@@ -618,3 +914,37 @@ fn expand_inputs(inputs: &[PathBuf], dir: &Path) -> io::Result<Vec<PathBuf>> {
 
     Ok(expanded_inputs)
 }
+
+#[test]
+fn is_crate_output_test() {
+    let source = |output: &str| -> SourceConfig {
+        toml::from_str(&format!("inputs = [\"test.jar\"]\noutput = {output:?}")).unwrap()
+    };
+    assert!(!source("src/lib.rs").is_crate_output());
+    assert!(!source("bindings").is_crate_output());
+    assert!(!source("out/bindings.v2").is_crate_output());
+    assert!(source("bindings/").is_crate_output());
+    assert!(source("out/bindings.v2/").is_crate_output());
+    assert!(source(env!("CARGO_MANIFEST_DIR")).is_crate_output());
+
+    let source = |options: &str| -> SourceConfig {
+        toml::from_str(&format!(
+            "inputs = [\"test.jar\"]\noutput = \"bindings\"\n{options}"
+        ))
+        .unwrap()
+    };
+    assert!(source("crate = true").is_crate_output());
+    assert!(!source("crate = false").is_crate_output());
+}
+
+#[test]
+fn reach_test() {
+    let rule = |options: &str| -> IncludeConfig {
+        toml::from_str(&format!("match = [\"a/**\"]\nbind = true\n{options}")).unwrap()
+    };
+    assert_eq!(rule("").reach(), None);
+    assert_eq!(rule("transitive = false").reach(), None);
+    assert_eq!(rule("transitive = true").reach(), Some(u32::MAX));
+    assert_eq!(rule("depth = 2").reach(), Some(2));
+    assert_eq!(rule("transitive = true\ndepth = 0").reach(), Some(0));
+}
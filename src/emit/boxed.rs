@@ -0,0 +1,120 @@
+use super::{
+    classes::Class,
+    cstring,
+    fields::{RustTypeFlavor, emit_fragment_type, emit_type},
+};
+use crate::{emit::Context, parser_util::Id};
+use cafebabe::descriptors::{FieldDescriptor, FieldType};
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+
+/// The primitive boxed by the `java.lang` wrapper class `class`, and the name of its unboxing method.
+fn wrapped_primitive(class: &str) -> Option<(FieldType<'static>, &'static str)> {
+    Some(match class {
+        "java/lang/Boolean" => (FieldType::Boolean, "booleanValue"),
+        "java/lang/Byte" => (FieldType::Byte, "byteValue"),
+        "java/lang/Character" => (FieldType::Char, "charValue"),
+        "java/lang/Short" => (FieldType::Short, "shortValue"),
+        "java/lang/Integer" => (FieldType::Integer, "intValue"),
+        "java/lang/Long" => (FieldType::Long, "longValue"),
+        "java/lang/Float" => (FieldType::Float, "floatValue"),
+        "java/lang/Double" => (FieldType::Double, "doubleValue"),
+        _ => return None,
+    })
+}
+
+/// If `descriptor` is a wrapper class implementing `crate::boxed::Boxed`, returns the descriptor of the primitive it
+/// boxes.
+pub fn boxed_primitive(
+    descriptor: &FieldDescriptor,
+    context: &Context,
+) -> Option<FieldDescriptor<'static>> {
+    let FieldType::Object(class_name) = &descriptor.field_type else {
+        return None;
+    };
+    let class: Id<'_> = Id::from(class_name);
+    if descriptor.dimensions != 0 || !context.is_bound_with_preamble(class.as_str()) {
+        return None;
+    }
+    let (primitive, _) = wrapped_primitive(class.as_str())?;
+    Some(FieldDescriptor {
+        dimensions: 0,
+        field_type: primitive,
+    })
+}
+
+impl Class {
+    /// Emits the `crate::boxed` impls of a wrapper class, boxing and unboxing through `valueOf` and `xxxValue`.
+    ///
+    /// Emits nothing for other classes.
+    pub fn write_boxed_impl(
+        &self,
+        context: &Context,
+        gate: &TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        let Some((primitive, unbox_method)) = wrapped_primitive(self.java.path().as_str()) else {
+            return Ok(TokenStream::new());
+        };
+        let primitive: FieldDescriptor<'static> = FieldDescriptor {
+            dimensions: 0,
+            field_type: primitive,
+        };
+
+        let mod_: &str = &self.rust.mod_;
+        let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let primitive_type: TokenStream = emit_type(
+            &primitive,
+            context,
+            mod_,
+            RustTypeFlavor::Bare,
+            &mut Vec::new(),
+        )?;
+
+        let box_descriptor: Literal =
+            cstring(&format!("({primitive})L{};", self.java.path().as_str()));
+        let unbox_descriptor: Literal = cstring(&format!("(){primitive}"));
+        let unbox_method: Literal = cstring(unbox_method);
+        let unbox_call: Ident = format_ident!("call_{}_method_a", emit_fragment_type(&primitive));
+
+        Ok(quote!(
+            #gate
+            unsafe impl crate::boxed::Boxed for #rust_name {
+                type Primitive = #primitive_type;
+
+                fn box_value<'env>(__jni_env: ::java_oxide::Env<'env>, value: #primitive_type) -> ::std::result::Result<::java_oxide::Local<'env, Self>, ::java_oxide::Local<'env, #throwable>> {
+                    static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                    unsafe {
+                        let __jni_args = [::java_oxide::AsJValue::as_jvalue(&value)];
+                        let __jni_class = Self::__class_global_ref(__jni_env);
+                        let __jni_method = __METHOD.get_or_init(||
+                            ::java_oxide::JMethodID::from_raw(__jni_env.require_static_method(__jni_class, c"valueOf", #box_descriptor))
+                        ).as_raw();
+                        __jni_env
+                            .call_static_object_method_a(__jni_class, __jni_method, __jni_args.as_ptr())
+                            .map(|boxed: ::std::option::Option<::java_oxide::Local<'env, Self>>| boxed.expect("valueOf never returns null"))
+                    }
+                }
+
+                fn unbox_value<'env>(self: &::java_oxide::Ref<'env, Self>) -> ::std::result::Result<#primitive_type, ::java_oxide::Local<'env, #throwable>> {
+                    static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                    unsafe {
+                        let __jni_env = self.env();
+                        let __jni_class = Self::__class_global_ref(__jni_env);
+                        let __jni_method = __METHOD.get_or_init(||
+                            ::java_oxide::JMethodID::from_raw(__jni_env.require_method(__jni_class, #unbox_method, #unbox_descriptor))
+                        ).as_raw();
+                        __jni_env.#unbox_call(self.as_raw(), __jni_method, [].as_ptr())
+                    }
+                }
+            }
+
+            #gate
+            impl crate::boxed::IntoBoxed<#rust_name> for #primitive_type {
+                fn into_boxed<'env>(self, env: ::java_oxide::Env<'env>) -> ::std::result::Result<crate::boxed::BoxedArg<'env, #rust_name>, ::java_oxide::Local<'env, #throwable>> {
+                    ::std::result::Result::Ok(crate::boxed::BoxedArg::Local(<#rust_name as crate::boxed::Boxed>::box_value(env, self)?))
+                }
+            }
+        ))
+    }
+}
@@ -1,6 +1,12 @@
-use super::{classes::Class, cstring, fields::RustTypeFlavor, methods::Method};
+use super::{
+    classes::Class, cstring, fields::RustTypeFlavor, futures::COMPLETION_CONSUMER, methods::Method,
+};
 use crate::{
-    emit::{Context, fields::emit_type},
+    config::ClassConfig,
+    emit::{
+        Context,
+        fields::{emit_fragment_type, emit_type, is_java_string, jni_function_type},
+    },
     parser_util::Id,
 };
 use cafebabe::descriptors::{FieldDescriptor, FieldType, ReturnDescriptor};
@@ -8,13 +14,26 @@ use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
 use std::fmt::Write;
 
+/// A method of a generated `{Class}Proxy` trait.
+pub struct ProxyMethod {
+    pub name: Ident,
+    /// Parameter types, after the `Env`.
+    pub arg_types: Vec<TokenStream>,
+    pub ret: TokenStream,
+    pub is_abstract: bool,
+    /// `#[cfg(...)]` of the method, see [`Context::member_cfg`].
+    pub cfg: TokenStream,
+}
+
 impl Class {
     #[allow(clippy::vec_init_then_push)]
     pub fn write_proxy(
         &self,
         context: &Context,
         methods: &[Method],
+        gate: &TokenStream,
     ) -> anyhow::Result<TokenStream> {
+        let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
         let mut emit_reject_reasons: Vec<String> = Vec::new();
 
         let mut out: TokenStream = TokenStream::new();
@@ -29,6 +48,9 @@ impl Class {
         let rust_proxy_name: Ident = format_ident!("{}Proxy", &self.rust.struct_name);
 
         let mut trait_methods: TokenStream = TokenStream::new();
+        let mut trait_signatures: Vec<ProxyMethod> = Vec::new();
+        // Gate and `(name, descriptor, function)` of every native method of the Java proxy class
+        let mut natives: Vec<(TokenStream, TokenStream)> = Vec::new();
 
         let java_proxy_path: String = format!(
             "{}/{}",
@@ -37,17 +59,12 @@ impl Class {
         );
 
         for method in methods {
+            if !method.is_proxied() {
+                continue;
+            }
             let Some(rust_name) = method.rust_name() else {
                 continue;
             };
-            if method.java.is_static()
-                || method.java.is_static_init()
-                || method.java.is_constructor()
-                || method.java.is_final()
-                || method.java.is_private()
-            {
-                continue;
-            }
 
             let mut native_params: Vec<FieldDescriptor<'_>> = Vec::new();
             native_params.push(FieldDescriptor {
@@ -62,6 +79,17 @@ impl Class {
             );
             let native_name: Ident = format_ident!("{native_name}");
             let rust_name: Ident = format_ident!("{rust_name}");
+            let cfg: TokenStream =
+                context.member_cfg(self.java.path(), &method.referenced_classes());
+
+            let native_java_name: Literal = cstring(&format!("native_{}", method.java.name()));
+            let native_descriptor: Literal =
+                cstring(&format!("(J{}", &method.java.descriptor().to_string()[1..]));
+            let trace_span: TokenStream = context.trace_span(
+                &java_proxy_path,
+                &format!("native_{}", method.java.name()),
+                &format!("(J{}", &method.java.descriptor().to_string()[1..]),
+            );
 
             let ret: TokenStream = match &method.java.descriptor.return_type {
                 ReturnDescriptor::Void => quote!(()),
@@ -75,20 +103,28 @@ impl Class {
             };
 
             let mut trait_args: TokenStream = TokenStream::new();
+            let mut trait_arg_types: Vec<TokenStream> = Vec::new();
             let mut native_args: TokenStream = TokenStream::new();
             let mut native_convert_args: TokenStream = TokenStream::new();
+            let mut native_lets: TokenStream = TokenStream::new();
+            let mut native_jvalues: TokenStream = TokenStream::new();
 
             for (arg_idx, arg) in method.java.descriptor.parameters.iter().enumerate() {
                 let arg_name: Ident = format_ident!("arg{}", arg_idx);
+                let rust_string: bool = cc.rust_strings && is_java_string(arg);
 
-                let trait_arg_type: TokenStream = emit_type(
-                    arg,
-                    context,
-                    &self.rust.mod_,
-                    RustTypeFlavor::OptionRef,
-                    &mut emit_reject_reasons,
-                )?;
+                let trait_arg_type: TokenStream = match rust_string {
+                    true => quote!(::std::option::Option<&str>),
+                    false => emit_type(
+                        arg,
+                        context,
+                        &self.rust.mod_,
+                        RustTypeFlavor::OptionRef,
+                        &mut emit_reject_reasons,
+                    )?,
+                };
                 trait_args.extend(quote!(#arg_name: #trait_arg_type,));
+                trait_arg_types.push(trait_arg_type);
 
                 let native_arg_type: TokenStream = emit_type(
                     arg,
@@ -98,33 +134,119 @@ impl Class {
                     &mut emit_reject_reasons,
                 )?;
                 native_args.extend(quote!(#arg_name: #native_arg_type,));
-                if matches!(arg.field_type, FieldType::Object(_)) || arg.dimensions > 0 {
+                if rust_string {
+                    native_lets.extend(quote!(
+                        let #arg_name = #arg_name.into_ref(__jni_env).map(|string| string.to_string_lossy());
+                    ));
+                    native_convert_args.extend(quote!(#arg_name.as_deref(),));
+                } else if matches!(arg.field_type, FieldType::Object(_)) || arg.dimensions > 0 {
                     native_convert_args.extend(quote!(#arg_name.into_ref(__jni_env),));
                 } else {
                     native_convert_args.extend(quote!(#arg_name,));
                 }
+                native_jvalues.extend(match emit_fragment_type(arg) {
+                    "object" => quote!(::java_oxide::sys::jvalue { l: #arg_name.as_raw() },),
+                    _ => quote!(::java_oxide::AsJValue::as_jvalue(&#arg_name),),
+                });
             }
 
-            trait_methods.extend(quote!(
-                fn #rust_name<'env>(
-                    &self,
-                    env: ::java_oxide::Env<'env>,
-                    #trait_args
-                ) -> #ret;
-            ));
+            let ret_fragment: &str = match &method.java.descriptor.return_type {
+                ReturnDescriptor::Void => "void",
+                ReturnDescriptor::Return(desc) => emit_fragment_type(desc),
+            };
+            if method.java.is_abstract() {
+                trait_methods.extend(quote!(
+                    #cfg
+                    fn #rust_name<'env>(
+                        &self,
+                        env: ::java_oxide::Env<'env>,
+                        #trait_args
+                    ) -> #ret;
+                ));
+            } else {
+                let ignored_args: Vec<Ident> = (0..trait_arg_types.len())
+                    .map(|arg_idx: usize| format_ident!("_arg{}", arg_idx))
+                    .collect();
+                let default_ret: TokenStream = match ret_fragment {
+                    "object" => quote!(::java_oxide::Return::null()),
+                    _ => quote!(::std::default::Default::default()),
+                };
+                trait_methods.extend(quote!(
+                    /// Defaults to the Java implementation, see [`crate::proxies::use_java_default`].
+                    #cfg
+                    fn #rust_name<'env>(
+                        &self,
+                        _env: ::java_oxide::Env<'env>,
+                        #(#ignored_args: #trait_arg_types,)*
+                    ) -> #ret {
+                        crate::proxies::use_java_default();
+                        #default_ret
+                    }
+                ));
+            }
+            trait_signatures.push(ProxyMethod {
+                name: rust_name.clone(),
+                arg_types: trait_arg_types,
+                ret: ret.clone(),
+                is_abstract: method.java.is_abstract(),
+                cfg: cfg.clone(),
+            });
 
+            natives.push((
+                cfg.clone(),
+                quote!((#native_java_name, #native_descriptor, #native_name as *mut ::std::os::raw::c_void)),
+            ));
+            let call: TokenStream = quote!(
+                let ptr: *const std::sync::Arc<dyn #rust_proxy_name> = ::std::ptr::with_exposed_provenance(ptr as usize);
+                unsafe {
+                    #native_lets
+                    (*ptr).#rust_name(__jni_env, #native_convert_args )
+                }
+            );
+            let body: TokenStream = match method.java.is_abstract() {
+                true => call,
+                false => {
+                    // Runs the overridden implementation if the trait method kept its default body
+                    let class_name: Ident = format_ident!("{}", &self.rust.struct_name);
+                    let java_name: Literal = cstring(method.java.name());
+                    let descriptor: Literal = cstring(&method.java.descriptor().to_string());
+                    let call_nonvirtual: Ident =
+                        format_ident!("CallNonvirtual{}MethodA", jni_function_type(ret_fragment));
+                    let result: TokenStream = match ret_fragment {
+                        "object" => quote!(::java_oxide::Return::from_raw(__jni_result)),
+                        _ => quote!(__jni_result),
+                    };
+                    quote!(
+                        let __jni_args = [#native_jvalues];
+                        let (__jni_result, __jni_java_default) = crate::proxies::dispatch(|| { #call });
+                        if !__jni_java_default {
+                            return __jni_result;
+                        }
+                        static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                        unsafe {
+                            let __jni_class = #class_name::__class_global_ref(__jni_env);
+                            let __jni_method = __METHOD.get_or_init(||
+                                ::java_oxide::JMethodID::from_raw(__jni_env.require_method(__jni_class, #java_name, #descriptor))
+                            ).as_raw();
+                            let __jni_raw_env = __jni_env.as_raw();
+                            let __jni_result = ((**__jni_raw_env).v1_1.#call_nonvirtual)(__jni_raw_env, __jni_this, __jni_class, __jni_method, __jni_args.as_ptr());
+                            #result
+                        }
+                    )
+                }
+            };
             out.extend(quote!(
+                #gate
+                #cfg
                 #[unsafe(no_mangle)]
                 extern "system" fn #native_name<'env>(
                     __jni_env: ::java_oxide::Env<'env>,
-                    _class: *mut (), // self class, ignore
+                    __jni_this: ::java_oxide::sys::jobject,
                     ptr: i64,
                     #native_args
                 ) -> #ret {
-                    let ptr: *const std::sync::Arc<dyn #rust_proxy_name> = ::std::ptr::with_exposed_provenance(ptr as usize);
-                    unsafe {
-                        (*ptr).#rust_name(__jni_env, #native_convert_args )
-                    }
+                    #trace_span
+                    #body
                 }
             ));
         }
@@ -137,18 +259,27 @@ impl Class {
         let native_name: String =
             mangle_native_method(&java_proxy_path, "native_finalize", &native_params);
         let native_name: Ident = format_ident!("{native_name}");
+        natives.push((
+            quote!(),
+            quote!((c"native_finalize", c"(J)V", #native_name as *mut ::std::os::raw::c_void)),
+        ));
+        let trace_span: TokenStream =
+            context.trace_span(&java_proxy_path, "native_finalize", "(J)V");
 
         out.extend(quote!(
+            #gate
             pub trait #rust_proxy_name: ::std::marker::Send + ::std::marker::Sync + 'static {
                 #trait_methods
             }
 
+            #gate
             #[unsafe(no_mangle)]
             extern "system" fn #native_name(
                 __jni_env: ::java_oxide::Env<'_>,
                 _class: *mut (), // self class, ignore
                 ptr: i64,
             ) {
+                #trace_span
                 let ptr: *mut std::sync::Arc<dyn #rust_proxy_name> = ::std::ptr::with_exposed_provenance_mut(ptr as usize);
                 let _ = unsafe { Box::from_raw(ptr) };
             }
@@ -192,16 +323,94 @@ impl Class {
             }
         ));
 
-        out.extend(quote!(impl #rust_name { #contents }));
+        let (native_gates, natives): (Vec<TokenStream>, Vec<TokenStream>) =
+            natives.into_iter().unzip();
+        contents.extend(quote!(
+            #[doc(hidden)]
+            pub fn __validate_proxy(__jni_env: ::java_oxide::Env, __report: &mut crate::validation::Report) {
+                let ::std::option::Option::Some(__jni_class) = __report.class(__jni_env, #java_proxy_path) else {
+                    return;
+                };
+                __report.method(__jni_env, &__jni_class, #java_proxy_path, c"<init>", c"(J)V", false);
+                #(
+                    #native_gates
+                    __report.natives(__jni_env, &__jni_class, #java_proxy_path, &[#natives]);
+                )*
+            }
+        ));
+
+        let abstract_methods: Vec<&ProxyMethod> = trait_signatures
+            .iter()
+            .filter(|method: &&ProxyMethod| method.is_abstract)
+            .collect();
+        if self.java.is_interface()
+            && let [
+                ProxyMethod {
+                    name: method_name,
+                    arg_types,
+                    ret,
+                    cfg,
+                    ..
+                },
+            ] = abstract_methods.as_slice()
+        {
+            let arg_names: Vec<Ident> = (0..arg_types.len())
+                .map(|arg_idx: usize| format_ident!("arg{}", arg_idx))
+                .collect();
+            let docs: String = format!(
+                "Implements this interface with the closure `f`, which handles every call to `{}`. Default methods keep \
+                 their Java implementation.",
+                method_name
+            );
+            contents.extend(quote!(
+                #[doc = #docs]
+                #cfg
+                pub fn from_fn<'local>(
+                    env: ::java_oxide::Env<'local>,
+                    f: impl for<'env> Fn(::java_oxide::Env<'env>, #(#arg_types),*) -> #ret + ::std::marker::Send + ::std::marker::Sync + 'static,
+                ) -> Result<::java_oxide::Local<'local, Self>, ::java_oxide::Local<'local, #throwable>> {
+                    struct __FnProxy<F>(F);
+
+                    impl<F> #rust_proxy_name for __FnProxy<F>
+                    where
+                        F: for<'env> Fn(::java_oxide::Env<'env>, #(#arg_types),*) -> #ret + ::std::marker::Send + ::std::marker::Sync + 'static,
+                    {
+                        fn #method_name<'env>(&self, env: ::java_oxide::Env<'env>, #(#arg_names: #arg_types),*) -> #ret {
+                            (self.0)(env, #(#arg_names),*)
+                        }
+                    }
+
+                    Self::new_proxy(env, ::std::sync::Arc::new(__FnProxy(f)))
+                }
+            ));
+        }
+
+        if self.java.path().as_str() == COMPLETION_CONSUMER {
+            out.extend(self.write_completion_impl(&trait_signatures, gate));
+        }
+
+        out.extend(quote!(#gate impl #rust_name { #contents }));
 
         if !emit_reject_reasons.is_empty() {
-            return Ok(TokenStream::new());
+            return Err(anyhow::anyhow!(emit_reject_reasons.join("\n")));
         }
 
         Ok(out)
     }
 }
 
+impl Method<'_> {
+    /// Whether the method is overridden by the `{Class}Proxy` trait.
+    pub fn is_proxied(&self) -> bool {
+        self.rust_name().is_some()
+            && !(self.java.is_static()
+                || self.java.is_static_init()
+                || self.java.is_constructor()
+                || self.java.is_final()
+                || self.java.is_private())
+    }
+}
+
 fn mangle_native_method(path: &str, name: &str, args: &[FieldDescriptor]) -> String {
     let mut res: String = String::new();
     res.push_str("Java_");
@@ -230,3 +439,36 @@ fn mangle_native(s: &str) -> String {
     }
     res
 }
+
+#[test]
+fn functional_interface_from_fn_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["**"]
+        bind = true
+
+        [[include]]
+        match = ["test/Consumer"]
+        proxy = true
+        "#,
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::interface("test/Consumer")
+                .method("accept", "(Ljava/lang/Object;)V")
+                .concrete_method("andThen", "(Ltest/Consumer;)Ltest/Consumer;"),
+        ],
+    );
+    let context: Context = fixture.context();
+
+    let consumer: String = context.all_classes["test/Consumer"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+    assert!(consumer.contains("fn from_fn <"));
+    // The default method keeps its Java implementation unless overridden
+    assert!(consumer.contains("crate :: proxies :: use_java_default ()"));
+    assert!(consumer.contains("CallNonvirtualObjectMethodA"));
+}
@@ -1,16 +1,24 @@
-use super::{cstring, fields::Field, known_docs_url::KnownDocsUrl, methods::Method};
+use super::{
+    cstring,
+    fields::Field,
+    futures::{UNPROXIED_COMPLETION_CONSUMER, has_completion_consumer},
+    known_docs_url::KnownDocsUrl,
+    manifest::{MemberRecords, NAME_TAKEN},
+    methods::Method,
+};
 use crate::{
-    config::ClassConfig,
+    config::{ClassConfig, OverloadStyle},
     emit::Context,
     identifiers::{FieldMangling, MethodManglingStyle, rust_ident},
     parser_util::{Id, IdPart, JavaClass},
     prelude::*,
 };
-use cafebabe::{FieldInfo, MethodInfo, descriptors::ClassName};
+use cafebabe::{FieldInfo, MethodInfo};
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
 use std::{
-    collections::{HashMap, HashSet},
+    cell::OnceCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Write,
 };
 
@@ -33,6 +41,17 @@ impl StructPaths {
 pub struct Class {
     pub rust: StructPaths,
     pub java: JavaClass,
+    /// Mangling of the bound methods, computed once by [`Class::bound_members`]
+    bound_methods: OnceCell<Result<Vec<BoundMethod>, String>>,
+}
+
+/// A method allowed by the config, as named by [`Class::bound_members`].
+#[derive(Debug)]
+struct BoundMethod {
+    /// Index of the method in the class file
+    index: usize,
+    mangling_style: MethodManglingStyle,
+    dispatched: bool,
 }
 
 impl Class {
@@ -68,10 +87,19 @@ impl Class {
     pub fn new(java: JavaClass) -> Result<Self, anyhow::Error> {
         let rust: StructPaths = StructPaths::new(java.path())?;
 
-        Ok(Self { rust, java })
+        Ok(Self {
+            rust,
+            java,
+            bound_methods: OnceCell::new(),
+        })
     }
 
-    pub fn write(&self, context: &Context) -> anyhow::Result<TokenStream> {
+    /// Emits the bindings of this class, which belongs to a `module` gated behind any of its features.
+    pub fn write(
+        &self,
+        context: &Context,
+        module: Option<&BTreeSet<String>>,
+    ) -> anyhow::Result<TokenStream> {
         let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
 
         // Ignored access_flags: SUPER, SYNTHETIC, ANNOTATION, ABSTRACT
@@ -104,10 +132,21 @@ impl Class {
         };
 
         let rust_name: Ident = format_ident!("{}", &self.rust.struct_name);
+        let gate: TokenStream = self.bindings_cfg(context, module);
 
         let referencetype_impl: TokenStream = match self.java.is_static() {
             true => quote!(),
-            false => quote!(unsafe impl ::java_oxide::ReferenceType for #rust_name {}),
+            false => quote!(
+                #gate
+                unsafe impl ::java_oxide::ReferenceType for #rust_name {}
+
+                #gate
+                unsafe impl crate::cast::BoundClass for #rust_name {
+                    fn class_global_ref(env: ::java_oxide::Env<'_>) -> ::java_oxide::sys::jobject {
+                        Self::__class_global_ref(env)
+                    }
+                }
+            ),
         };
 
         let mut out: TokenStream = TokenStream::new();
@@ -116,11 +155,13 @@ impl Class {
 
         out.extend(quote!(
             #[doc = #docs]
+            #gate
             #attributes
             #visibility enum #rust_name {}
 
             #referencetype_impl
 
+            #gate
             unsafe impl ::java_oxide::JniType for #rust_name {
                 fn static_with_jni_type<R>(callback: impl FnOnce(&::std::ffi::CStr) -> R) -> R {
                     callback(#java_path)
@@ -128,27 +169,26 @@ impl Class {
             }
         ));
 
-        // recursively visit all superclasses and superinterfaces.
-        let mut queue: Vec<Id<'_>> = Vec::new();
-        let mut visited: HashSet<Id<'_>> = HashSet::new();
-        queue.push(self.java.path());
-        visited.insert(self.java.path());
+        // recursively visit all superclasses and superinterfaces, including those bound by `[[extern]]` crates.
+        let mut queue: Vec<String> = vec![self.java.path().as_str().to_owned()];
+        let mut visited: HashSet<String> = queue.iter().cloned().collect();
+        let mut ancestors: Vec<Id<'_>> = Vec::new();
         while let Some(path) = queue.pop() {
-            let class: &std::rc::Rc<Class> = context.all_classes.get(path.as_str()).unwrap();
-            for path2 in self
-                .java
-                .interfaces()
-                .map(|i: &ClassName<'_>| Id(i))
-                .chain(class.java.super_path())
-            {
-                if context.all_classes.contains_key(path2.as_str()) && !visited.contains(&path2) {
-                    let rust_path: TokenStream =
-                        context.java_to_rust_path(path2, &self.rust.mod_).unwrap();
+            for path2 in context.supertypes(&path) {
+                if context.is_bound(&path2) && !visited.contains(&path2) {
+                    let rust_path: TokenStream = context
+                        .java_to_rust_path(Id(&path2), &self.rust.mod_)
+                        .unwrap();
                     out.extend(quote!(
+                        #gate
                         unsafe impl ::java_oxide::AssignableTo<#rust_path> for #rust_name {}
                     ));
+                    ancestors.push(match context.all_classes.get(&path2) {
+                        Some(class) => class.java.path(),
+                        None => Id(&context.externs[&path2].class.java),
+                    });
+                    visited.insert(path2.clone());
                     queue.push(path2);
-                    visited.insert(path2);
                 }
             }
         }
@@ -172,26 +212,50 @@ impl Class {
             }
         ));
 
-        let mut methods: Vec<Method> = self
-            .java
-            .methods()
-            .map(|m: &MethodInfo<'_>| Method::new(&self.java, m))
-            .filter(|m: &Method<'_>| {
-                (m.java.is_public() || cc.bind_private_methods) && !m.java.is_bridge()
-            })
-            .collect();
-        let mut fields: Vec<Field> = self
-            .java
-            .fields()
-            .map(|f: &FieldInfo<'_>| Field::new(&self.java, f))
-            .filter(|f: &Field<'_>| f.java.is_public() || cc.bind_private_fields)
+        let (mut methods, mut fields) = self.bound_members(&cc)?;
+        let mut taken_names: HashSet<String> = self
+            .fill_name_counts(&methods, &fields)
+            .into_keys()
+            .chain(
+                methods
+                    .iter()
+                    .filter_map(|m: &Method<'_>| match m.is_dispatched() {
+                        true => m.dispatch_name(),
+                        false => None,
+                    }),
+            )
             .collect();
 
-        self.resolve_collisions(&mut methods, &fields)?;
+        if taken_names.insert("class_object".to_owned()) {
+            let class_object: TokenStream = match context.is_bound("java/lang/Class") {
+                true => context.java_to_rust_path(Id("java/lang/Class"), &self.rust.mod_)?,
+                false => object.clone(),
+            };
+            // Hand out a new local reference, the cached global one must outlive every caller.
+            contents.extend(quote!(
+                /// Returns this class' `java.lang.Class`, as cached by the bindings.
+                pub fn class_object<'env>(__jni_env: ::java_oxide::Env<'env>) -> ::java_oxide::Local<'env, #class_object> {
+                    unsafe {
+                        let __jni_class = Self::__class_global_ref(__jni_env);
+                        let __jni_raw_env = __jni_env.as_raw();
+                        ::java_oxide::Local::from_raw(__jni_env, ((**__jni_raw_env).v1_2.NewLocalRef)(__jni_raw_env, __jni_class))
+                    }
+                }
+            ));
+        }
 
+        let mut records: MemberRecords = MemberRecords::default();
+        let mut validation_checks: TokenStream = TokenStream::new();
         for method in &mut methods {
-            match method.emit(context, &cc, &self.rust.mod_) {
-                Ok(res) => contents.extend(res),
+            let result: anyhow::Result<TokenStream> = method.emit(context, &cc, &self.rust.mod_);
+            records.method(context, method, &result);
+            match result {
+                Ok(res) => {
+                    let cfg: TokenStream =
+                        context.member_cfg(self.java.path(), &method.referenced_classes());
+                    contents.extend(quote!(#cfg #res));
+                    validation_checks.extend(method.validation_check());
+                }
                 Err(e) => trace!(
                     "METHOD REJECTED - \"{}.{}()\":\n{}",
                     self.java.path().as_str(),
@@ -201,9 +265,83 @@ impl Class {
             };
         }
 
+        let mod_: &str = &self.rust.mod_;
+        contents.extend(self.write_variants(
+            context,
+            "STRING",
+            &methods,
+            &mut taken_names,
+            &mut records,
+            |method: &Method| method.string_variant_name(&cc),
+            |method: &Method| method.emit_string_variant(context, &cc, mod_),
+        ));
+        contents.extend(self.write_variants(
+            context,
+            "OPTION",
+            &methods,
+            &mut taken_names,
+            &mut records,
+            |method: &Method| method.option_variant_name(context, &cc),
+            |method: &Method| method.emit_option_variant(context, &cc, mod_),
+        ));
+        if has_completion_consumer(context) {
+            contents.extend(self.write_variants(
+                context,
+                "ASYNC",
+                &methods,
+                &mut taken_names,
+                &mut records,
+                |method: &Method| method.async_variant_name(context),
+                |method: &Method| method.emit_async_variant(context, &cc, mod_),
+            ));
+        } else {
+            for method in &methods {
+                if let Some(name) = method.async_variant_name(context) {
+                    records.skipped(
+                        method.java.name(),
+                        &method.java.descriptor().to_string(),
+                        &name,
+                        UNPROXIED_COMPLETION_CONSUMER,
+                    );
+                }
+            }
+        }
+        contents.extend(self.write_variants(
+            context,
+            "SUPER",
+            &methods,
+            &mut taken_names,
+            &mut records,
+            Method::super_variant_name,
+            |method: &Method| method.emit_super_variant(context, &cc, mod_),
+        ));
+        contents.extend(self.write_variants(
+            context,
+            "UNCHECKED",
+            &methods,
+            &mut taken_names,
+            &mut records,
+            |method: &Method| method.unchecked_variant_name(&cc),
+            |method: &Method| method.emit_unchecked_variant(context, &cc, mod_),
+        ));
+        contents.extend(self.write_variants(
+            context,
+            "VARARGS",
+            &methods,
+            &mut taken_names,
+            &mut records,
+            Method::varargs_name,
+            |method: &Method| method.emit_varargs(context, &cc, mod_),
+        ));
+
         for field in &mut fields {
-            match field.emit(context, &cc, &self.rust.mod_) {
-                Ok(res) => contents.extend(res),
+            let result: anyhow::Result<TokenStream> = field.emit(context, &cc, &self.rust.mod_);
+            records.field(context, field, &result);
+            match result {
+                Ok(res) => {
+                    contents.extend(res);
+                    validation_checks.extend(field.validation_check());
+                }
                 Err(e) => trace!(
                     "FIELD REJECTED - \"{}.{}\":\n{}",
                     self.java.path().as_str(),
@@ -213,15 +351,209 @@ impl Class {
             };
         }
 
-        out.extend(quote!(impl #rust_name { #contents }));
-
-        if cc.proxy {
-            out.extend(self.write_proxy(context, &methods)?);
+        if cc.fallible_lookups {
+            contents.extend(self.write_lookups(
+                context,
+                &cc,
+                &methods,
+                &fields,
+                &mut taken_names,
+                &mut records,
+            )?);
         }
 
+        let (dispatch, overload_traits) = self.write_overload_dispatch(context, &methods, &gate)?;
+        contents.extend(dispatch);
+
+        contents.extend(self.write_upcasts(context, &ancestors, &mut taken_names)?);
+        contents.extend(self.write_inherited_methods(context, &methods, &mut taken_names)?);
+        contents.extend(self.write_collection_adapters(context, &mut taken_names)?);
+
+        let proxy_result: Option<anyhow::Result<TokenStream>> =
+            cc.proxy.then(|| self.write_proxy(context, &methods, &gate));
+        self.record(
+            context,
+            &cc,
+            records,
+            proxy_result
+                .as_ref()
+                .map(|result: &anyhow::Result<TokenStream>| (result, methods.as_slice())),
+        );
+        let proxy: TokenStream = match proxy_result {
+            Some(Ok(res)) => res,
+            Some(Err(e)) => {
+                trace!("PROXY REJECTED - \"{}\":\n{}", self.java.path().as_str(), e);
+                TokenStream::new()
+            }
+            None => TokenStream::new(),
+        };
+        contents.extend(self.write_validation(validation_checks, !proxy.is_empty()));
+
+        out.extend(quote!(#gate impl #rust_name { #contents }));
+        out.extend(overload_traits);
+        out.extend(self.write_interface_trait(context, &gate)?);
+        out.extend(self.write_boxed_impl(context, &gate)?);
+        out.extend(self.write_optional_impl(context, &gate)?);
+        out.extend(self.write_contract_impls(context, &ancestors, &gate)?);
+
+        out.extend(proxy);
+
         Ok(out)
     }
 
+    /// Emits a variant of every method which `name` gives one, through `emit`, unless that name is already taken.
+    ///
+    /// `kind` prefixes the log lines of the skipped and rejected variants, and the skipped ones are added to `records`.
+    #[allow(clippy::too_many_arguments)]
+    fn write_variants<'m>(
+        &self,
+        context: &Context,
+        kind: &str,
+        methods: &'m [Method<'m>],
+        taken_names: &mut HashSet<String>,
+        records: &mut MemberRecords,
+        name: impl Fn(&Method<'m>) -> Option<String>,
+        emit: impl Fn(&Method<'m>) -> anyhow::Result<TokenStream>,
+    ) -> TokenStream {
+        let mut out: TokenStream = TokenStream::new();
+        for method in methods {
+            let Some(name) = name(method) else {
+                continue;
+            };
+            if !taken_names.insert(name.clone()) {
+                trace!(
+                    "{kind} METHOD SKIPPED - \"{}.{}()\": {} is already taken",
+                    self.java.path().as_str(),
+                    method.java.name(),
+                    name
+                );
+                records.skipped(
+                    method.java.name(),
+                    &method.java.descriptor().to_string(),
+                    &name,
+                    NAME_TAKEN,
+                );
+                continue;
+            }
+            match emit(method) {
+                Ok(res) => {
+                    let cfg: TokenStream =
+                        context.member_cfg(self.java.path(), &method.referenced_classes());
+                    out.extend(quote!(#cfg #res));
+                }
+                Err(e) => trace!(
+                    "{kind} METHOD REJECTED - \"{}.{}()\":\n{}",
+                    self.java.path().as_str(),
+                    method.java.name(),
+                    e
+                ),
+            };
+        }
+        out
+    }
+
+    /// Collects the methods and fields that `cc`, the config of this class, allows binding, with method name
+    /// collisions resolved.
+    ///
+    /// The method names are only resolved once per class, as every subclass forwarding inherited methods needs them.
+    pub fn bound_members(
+        &self,
+        cc: &ClassConfig,
+    ) -> anyhow::Result<(Vec<Method<'_>>, Vec<Field<'_>>)> {
+        let fields: Vec<Field> = self
+            .java
+            .fields()
+            .map(|f: &FieldInfo<'_>| Field::new(&self.java, f))
+            .filter(|f: &Field<'_>| f.java.is_public() || cc.bind_private_fields)
+            .collect();
+
+        let bound_methods: &[BoundMethod] = self
+            .bound_methods
+            .get_or_init(|| self.name_methods(cc, &fields).map_err(|e| e.to_string()))
+            .as_deref()
+            .map_err(|e: &String| anyhow::anyhow!("{e}"))?;
+        let all_methods: Vec<&MethodInfo<'_>> = self.java.methods().collect();
+        let methods: Vec<Method> = bound_methods
+            .iter()
+            .map(|bound: &BoundMethod| {
+                let mut method: Method = Method::new(&self.java, all_methods[bound.index]);
+                method.set_mangling_style(bound.mangling_style);
+                if bound.dispatched {
+                    method.set_dispatched();
+                }
+                method
+            })
+            .collect();
+
+        Ok((methods, fields))
+    }
+
+    /// Resolves the names of the methods that `cc` allows binding, see [`Class::bound_members`].
+    fn name_methods(&self, cc: &ClassConfig, fields: &[Field]) -> anyhow::Result<Vec<BoundMethod>> {
+        let (indices, mut methods): (Vec<usize>, Vec<Method>) = self
+            .java
+            .methods()
+            .map(|m: &MethodInfo<'_>| Method::new(&self.java, m))
+            .enumerate()
+            .filter(|(_, m): &(usize, Method<'_>)| {
+                (m.java.is_public() || cc.bind_private_methods) && !m.java.is_bridge()
+            })
+            .unzip();
+
+        self.resolve_collisions(&mut methods, fields)?;
+        if cc.overloads == OverloadStyle::Dispatch {
+            self.dispatch_overloads(&mut methods, fields);
+        }
+
+        Ok(indices
+            .into_iter()
+            .zip(methods)
+            .map(|(index, method): (usize, Method<'_>)| BoundMethod {
+                index,
+                mangling_style: method.mangling_style(),
+                dispatched: method.is_dispatched(),
+            })
+            .collect())
+    }
+
+    /// Hides overloads behind a single dispatching method per Java name, see [`OverloadStyle::Dispatch`].
+    ///
+    /// Names shared by static and instance methods, or taken by a field, keep their mangled overloads.
+    fn dispatch_overloads(&self, methods: &mut [Method], fields: &[Field]) {
+        let field_names: HashMap<String, usize> = self.fill_name_counts(&[], fields);
+
+        let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (idx, method) in methods.iter().enumerate() {
+            if method.java.is_static_init() {
+                continue;
+            }
+            if let Some(name) = method.dispatch_name() {
+                groups.entry(name).or_default().push(idx);
+            }
+        }
+
+        let is_static = |m: &Method| m.java.is_static() || m.java.is_constructor();
+        for (name, overloads) in groups {
+            if overloads.len() < 2 || field_names.contains_key(&name) {
+                continue;
+            }
+            if !overloads
+                .iter()
+                .all(|&idx| is_static(&methods[idx]) == is_static(&methods[overloads[0]]))
+            {
+                trace!(
+                    "OVERLOADS NOT DISPATCHED - \"{}.{}\": mixes static and instance methods",
+                    self.java.path().as_str(),
+                    name
+                );
+                continue;
+            }
+            for idx in overloads {
+                methods[idx].set_dispatched();
+            }
+        }
+    }
+
     /// Fills the name_counts map with all field and method names
     fn fill_name_counts(&self, methods: &[Method], fields: &[Field]) -> HashMap<String, usize> {
         let mut name_counts: HashMap<String, usize> = HashMap::new();
@@ -0,0 +1,252 @@
+use super::classes::Class;
+use crate::{
+    emit::Context,
+    parser_util::{
+        Id,
+        signature::{
+            ClassSignature, ClassTypeSignature, TypeArgument, TypeSignature, parse_class_signature,
+        },
+    },
+    prelude::*,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    iter,
+};
+
+const ITERABLE: &str = "java/lang/Iterable";
+const COLLECTION: &str = "java/util/Collection";
+const LIST: &str = "java/util/List";
+const MAP: &str = "java/util/Map";
+
+/// Type arguments resolved to JNI class paths, `None` where the type is erased or not a plain class.
+type TypeArgs = Vec<Option<String>>;
+
+impl Class {
+    /// Resolves the type arguments this class passes to `target`, one of its generic superclasses or superinterfaces,
+    /// by following the `Signature`s of bound classes.
+    ///
+    /// Returns `None` if `target` isn't reachable through bound classes.
+    pub fn generic_args_of(&self, context: &Context, target: &str) -> Option<TypeArgs> {
+        find_generic_args(
+            context,
+            self.java.path().as_str(),
+            Vec::new(),
+            target,
+            &mut HashSet::new(),
+        )
+    }
+
+    /// Emits Rust adapters for bound classes implementing `Iterable`, `Collection`, `List` or `Map`, along with
+    /// `from_iter` constructors for `ArrayList` and `HashMap`.
+    ///
+    /// Element types come from the generic signatures, falling back to `Object` when erased. Adapters whose name is
+    /// already taken are skipped.
+    pub fn write_collection_adapters(
+        &self,
+        context: &Context,
+        taken_names: &mut HashSet<String>,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+        if self.java.is_static() {
+            return Ok(out);
+        }
+
+        let mod_: &str = &self.rust.mod_;
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        // The Rust type of a type argument, and the `#[cfg(...)]` of the adapters using it
+        let type_arg =
+            |args: &TypeArgs, idx: usize| -> anyhow::Result<(TokenStream, TokenStream)> {
+                let path: &str = match args.get(idx) {
+                    Some(Some(path)) if context.is_bound(path.as_str()) => path,
+                    _ => "java/lang/Object",
+                };
+                let cfg: TokenStream =
+                    context.member_cfg(self.java.path(), &BTreeSet::from([path.to_owned()]));
+                Ok((context.java_to_rust_path(Id(path), mod_)?, cfg))
+            };
+        let mut claim = |name: &str| -> bool {
+            let free: bool = taken_names.insert(name.to_owned());
+            if !free {
+                trace!(
+                    "COLLECTION ADAPTER SKIPPED - \"{}\": {} is already taken",
+                    self.java.path().as_str(),
+                    name
+                );
+            }
+            free
+        };
+
+        if let Some(args) = self.generic_args_of(context, ITERABLE)
+            && claim("iter")
+        {
+            let (element, cfg) = type_arg(&args, 0)?;
+            out.extend(quote!(
+                /// Iterates over the elements of this `java.lang.Iterable`, through `iterator()`.
+                #cfg
+                pub fn iter<'env>(self: &::java_oxide::Ref<'env, Self>) -> ::std::result::Result<crate::collections::JavaIter<'env, #element>, ::java_oxide::Local<'env, #throwable>> {
+                    crate::collections::iter(self.env(), self.as_raw())
+                }
+            ));
+        }
+
+        let is_map: bool = self.generic_args_of(context, MAP).is_some();
+        if (is_map || self.generic_args_of(context, COLLECTION).is_some()) && claim("len") {
+            out.extend(quote!(
+                /// The number of elements in this collection, through `size()`.
+                pub fn len<'env>(self: &::java_oxide::Ref<'env, Self>) -> ::std::result::Result<usize, ::java_oxide::Local<'env, #throwable>> {
+                    crate::collections::len(self.env(), self.as_raw(), #is_map)
+                }
+            ));
+        }
+
+        if let Some(args) = self.generic_args_of(context, LIST)
+            && claim("get_at")
+        {
+            let (element, cfg) = type_arg(&args, 0)?;
+            out.extend(quote!(
+                /// The element at `index` in this list, through `get(int)`.
+                #cfg
+                pub fn get_at<'env>(self: &::java_oxide::Ref<'env, Self>, index: usize) -> ::std::result::Result<::std::option::Option<::java_oxide::Local<'env, #element>>, ::java_oxide::Local<'env, #throwable>> {
+                    crate::collections::get(self.env(), self.as_raw(), index)
+                }
+            ));
+        }
+
+        let (object, _) = type_arg(&Vec::new(), 0)?;
+        match self.java.path().as_str() {
+            "java/util/ArrayList" if claim("from_iter") => out.extend(quote!(
+                /// Builds a new `ArrayList` holding `elements`.
+                pub fn from_iter<'env>(
+                    __jni_env: ::java_oxide::Env<'env>,
+                    elements: impl ::std::iter::IntoIterator<Item = impl ::java_oxide::AsArg<#object>>,
+                ) -> ::std::result::Result<::java_oxide::Local<'env, Self>, ::java_oxide::Local<'env, #throwable>> {
+                    crate::collections::array_list_from_iter::<Self, #object>(__jni_env, elements)
+                }
+            )),
+            "java/util/HashMap" if claim("from_iter") => out.extend(quote!(
+                /// Builds a new `HashMap` holding `entries`.
+                pub fn from_iter<'env>(
+                    __jni_env: ::java_oxide::Env<'env>,
+                    entries: impl ::std::iter::IntoIterator<Item = (impl ::java_oxide::AsArg<#object>, impl ::java_oxide::AsArg<#object>)>,
+                ) -> ::std::result::Result<::java_oxide::Local<'env, Self>, ::java_oxide::Local<'env, #throwable>> {
+                    crate::collections::hash_map_from_iter::<Self, #object, #object>(__jni_env, entries)
+                }
+            )),
+            _ => {}
+        }
+
+        Ok(out)
+    }
+}
+
+fn find_generic_args(
+    context: &Context,
+    path: &str,
+    args: TypeArgs,
+    target: &str,
+    visited: &mut HashSet<String>,
+) -> Option<TypeArgs> {
+    if path == target {
+        return Some(args);
+    }
+    if !visited.insert(path.to_owned()) {
+        return None;
+    }
+    if !context.is_bound(path) {
+        return None;
+    }
+
+    let supers: Vec<(String, TypeArgs)> =
+        match context.class_signature(path).map(parse_class_signature) {
+            Some(Ok(signature)) => {
+                let ClassSignature {
+                    type_params,
+                    superclass,
+                    interfaces,
+                } = signature;
+                let bindings: HashMap<String, Option<String>> = type_params
+                    .into_iter()
+                    .zip(args.into_iter().chain(iter::repeat(None)))
+                    .collect();
+                iter::once(superclass)
+                    .chain(interfaces)
+                    .map(|s: ClassTypeSignature| {
+                        let args: TypeArgs = s
+                            .type_args
+                            .iter()
+                            .map(|arg: &TypeArgument| resolve_type_arg(arg, &bindings))
+                            .collect();
+                        (s.path, args)
+                    })
+                    .collect()
+            }
+            res => {
+                if let Some(Err(e)) = res {
+                    debug!("Failed to parse generic signature of \"{path}\": {e}");
+                }
+                context
+                    .supertypes(path)
+                    .into_iter()
+                    .map(|path: String| (path, Vec::new()))
+                    .collect()
+            }
+        };
+
+    supers
+        .into_iter()
+        .find_map(|(path, args)| find_generic_args(context, &path, args, target, visited))
+}
+
+fn resolve_type_arg(
+    arg: &TypeArgument,
+    bindings: &HashMap<String, Option<String>>,
+) -> Option<String> {
+    match arg {
+        TypeArgument::Exact(ty) | TypeArgument::Extends(ty) => match ty {
+            TypeSignature::Class(class) => Some(class.path.clone()),
+            TypeSignature::TypeVariable(name) => bindings.get(name).cloned().flatten(),
+            TypeSignature::Base(_) | TypeSignature::Array(_) => None,
+        },
+        TypeArgument::Any | TypeArgument::Super(_) => None,
+    }
+}
+
+#[test]
+fn list_adapters_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["java/**"]
+        bind = true
+        "#,
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::interface("java/lang/Iterable")
+                .signature("<T:Ljava/lang/Object;>Ljava/lang/Object;")
+                .method("iterator", "()Ljava/util/Iterator;"),
+            ClassBuilder::interface("java/util/Collection")
+                .signature("<E:Ljava/lang/Object;>Ljava/lang/Object;Ljava/lang/Iterable<TE;>;")
+                .implements("java/lang/Iterable")
+                .method("size", "()I"),
+            ClassBuilder::interface("java/util/List")
+                .signature("<E:Ljava/lang/Object;>Ljava/lang/Object;Ljava/util/Collection<TE;>;")
+                .implements("java/util/Collection")
+                .method("get", "(I)Ljava/lang/Object;"),
+        ],
+    );
+    let context: Context = fixture.context();
+
+    let list: String = context.all_classes["java/util/List"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+    for adapter in ["fn iter <", "fn len <", "fn get_at <"] {
+        assert!(list.contains(adapter), "missing {adapter:?}");
+    }
+    assert!(list.contains("fn get <"));
+}
@@ -0,0 +1,41 @@
+use super::classes::Class;
+use crate::{emit::Context, parser_util::Id};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+impl Class {
+    /// Emits the `crate::contracts` markers of this class: `Comparable` and `AutoCloseable` if it or one of its bound
+    /// `ancestors` implements the matching Java interface.
+    pub fn write_contract_impls(
+        &self,
+        context: &Context,
+        ancestors: &[Id<'_>],
+        gate: &TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+        if self.java.is_static() {
+            return Ok(out);
+        }
+
+        // Direct supertypes of the class and its bound ancestors, bound or not
+        let mut supertypes: Vec<String> = vec![self.java.path().as_str().to_owned()];
+        for path in std::iter::once(self.java.path()).chain(ancestors.iter().copied()) {
+            supertypes.extend(context.supertypes(path.as_str()));
+        }
+        let implements = |interfaces: &[&str]| {
+            supertypes
+                .iter()
+                .any(|path: &String| interfaces.contains(&path.as_str()))
+        };
+
+        let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+        if implements(&["java/lang/Comparable"]) {
+            out.extend(quote!(#gate unsafe impl crate::contracts::Comparable for #rust_name {}));
+        }
+        if implements(&["java/lang/AutoCloseable", "java/io/Closeable"]) {
+            out.extend(quote!(#gate unsafe impl crate::contracts::AutoCloseable for #rust_name {}));
+        }
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,285 @@
+//! Cargo features gating the bindings of groups of Java classes, see `sources.features`.
+
+use super::{Context, classes::Class, fields::Field, methods::Method, modules::Module};
+use crate::{
+    config::ClassConfig,
+    parser_util::{Id, signature::parse_class_signature},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    iter,
+};
+
+/// The Cargo feature of every gated class, and the features they need.
+#[derive(Debug, Default)]
+pub struct FeatureGraph {
+    /// Features enabled by each feature, for the supertypes of its classes and the types their members reference
+    deps: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl FeatureGraph {
+    pub fn new(context: &Context) -> Self {
+        let mut graph: FeatureGraph = FeatureGraph::default();
+        let mut paths: Vec<&String> = context.all_classes.keys().collect();
+        paths.sort();
+
+        // Supertypes are always needed, member types only while they don't make features enable themselves, which
+        // Cargo rejects: the members referencing them are gated behind their feature instead.
+        let mut member_deps: BTreeSet<(String, String)> = BTreeSet::new();
+        for path in paths {
+            let Some(feature) = context.feature_of(path) else {
+                continue;
+            };
+            let deps: &mut BTreeSet<String> = graph.deps.entry(feature.clone()).or_default();
+            for supertype in context.supertypes(path) {
+                if let Some(dep) = context.feature_of(&supertype)
+                    && dep != feature
+                {
+                    deps.insert(dep);
+                }
+            }
+            for referenced in context.all_classes[path].referenced_classes(context) {
+                if let Some(dep) = context.feature_of(&referenced)
+                    && dep != feature
+                {
+                    member_deps.insert((feature.clone(), dep));
+                }
+            }
+        }
+        for (feature, dep) in member_deps {
+            if !graph.implied(Some(&dep)).contains(&feature) {
+                graph.deps.entry(feature).or_default().insert(dep);
+            }
+        }
+        graph
+    }
+
+    /// The `[features]` table of the bindings crate, plus a `full` feature enabling everything.
+    pub fn manifest_table(&self) -> String {
+        let mut table: String = String::new();
+        if self.deps.is_empty() {
+            return table;
+        }
+        let quoted = |features: &mut dyn Iterator<Item = &String>| -> String {
+            features
+                .map(|f: &String| format!("{f:?}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        writeln!(table, "full = [{}]", quoted(&mut self.deps.keys())).unwrap();
+        for (feature, deps) in &self.deps {
+            writeln!(table, "{feature:?} = [{}]", quoted(&mut deps.iter())).unwrap();
+        }
+        table
+    }
+
+    /// `feature` and every feature it enables, directly or not.
+    pub fn implied(&self, feature: Option<&str>) -> BTreeSet<String> {
+        let mut implied: BTreeSet<String> = BTreeSet::new();
+        let mut queue: Vec<String> = feature.into_iter().map(str::to_owned).collect();
+        while let Some(feature) = queue.pop() {
+            if let Some(deps) = self.deps.get(&feature) {
+                queue.extend(
+                    deps.iter()
+                        .filter(|d: &&String| !implied.contains(*d))
+                        .cloned(),
+                );
+            }
+            implied.insert(feature);
+        }
+        implied
+    }
+}
+
+impl Context<'_> {
+    /// The feature gating the class `path`, if it's bound here and gated.
+    pub fn feature_of(&self, path: &str) -> Option<String> {
+        match self.all_classes.contains_key(path) {
+            true => self.config.feature_for(path),
+            false => None,
+        }
+    }
+
+    /// `#[cfg(...)]` for a member of the bindings of `class` referencing the classes `referenced`, requiring their
+    /// features beyond those `class` already enables.
+    pub fn member_cfg(&self, class: Id, referenced: &BTreeSet<String>) -> TokenStream {
+        cfg(&self.member_features(class, referenced))
+    }
+
+    /// The features [`Self::member_cfg`] requires.
+    pub fn member_features(&self, class: Id, referenced: &BTreeSet<String>) -> BTreeSet<String> {
+        if !self.config.src.features {
+            return BTreeSet::new();
+        }
+        let implied: BTreeSet<String> = self
+            .features()
+            .implied(self.feature_of(class.as_str()).as_deref());
+        referenced
+            .iter()
+            .filter_map(|path: &String| self.feature_of(path))
+            .filter(|feature: &String| !implied.contains(feature))
+            .collect()
+    }
+}
+
+impl Class {
+    /// `#[cfg(...)]` gating the bindings of this class behind its feature, unless its `module` is already gated
+    /// behind it alone.
+    pub fn bindings_cfg(
+        &self,
+        context: &Context,
+        module: Option<&BTreeSet<String>>,
+    ) -> TokenStream {
+        let Some(feature) = context.feature_of(self.java.path().as_str()) else {
+            return quote!();
+        };
+        match module {
+            Some(module) if module.len() == 1 && module.first() == Some(&feature) => quote!(),
+            _ => cfg(&BTreeSet::from([feature])),
+        }
+    }
+
+    /// JNI paths of the classes referenced by the generic supertypes and the bound members of this class.
+    fn referenced_classes(&self, context: &Context) -> BTreeSet<String> {
+        let mut referenced: BTreeSet<String> = BTreeSet::new();
+        if let Some(signature) = self
+            .java
+            .signature()
+            .and_then(|signature: &str| parse_class_signature(signature).ok())
+        {
+            for supertype in iter::once(&signature.superclass).chain(&signature.interfaces) {
+                supertype.collect_classes(&mut referenced);
+            }
+        }
+        let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
+        if let Ok((methods, fields)) = self.bound_members(&cc) {
+            referenced.extend(methods.iter().flat_map(Method::referenced_classes));
+            referenced.extend(fields.iter().flat_map(Field::referenced_classes));
+        }
+        referenced
+    }
+}
+
+impl Module {
+    /// The features gating this module: any of those of its classes, or `None` if one of them isn't gated.
+    pub fn features(&self, context: &Context) -> Option<BTreeSet<String>> {
+        let mut features: BTreeSet<String> = BTreeSet::new();
+        for class in self.classes.values() {
+            features.insert(context.config.feature_for(class.java.path().as_str())?);
+        }
+        for module in self.modules.values() {
+            features.extend(module.features(context)?);
+        }
+        Some(features)
+    }
+}
+
+/// `#[cfg(...)]` requiring every feature of `features`, or nothing if it's empty.
+pub fn cfg(features: &BTreeSet<String>) -> TokenStream {
+    match features.len() {
+        0 => quote!(),
+        1 => {
+            let feature: &String = features.first().unwrap();
+            quote!(#[cfg(feature = #feature)])
+        }
+        _ => {
+            let features = features.iter();
+            quote!(#[cfg(all(#(feature = #features),*))])
+        }
+    }
+}
+
+/// `#[cfg(...)]` requiring any feature of `features`, or nothing if it's empty.
+pub fn cfg_any(features: &BTreeSet<String>) -> TokenStream {
+    match features.len() {
+        0 | 1 => cfg(features),
+        _ => {
+            let features = features.iter();
+            quote!(#[cfg(any(#(feature = #features),*))])
+        }
+    }
+}
+
+#[test]
+fn cfg_test() {
+    let features = |names: &[&str]| -> BTreeSet<String> {
+        names.iter().map(|name: &&str| name.to_string()).collect()
+    };
+    assert!(cfg(&features(&[])).is_empty());
+    assert_eq!(
+        cfg(&features(&["a"])).to_string(),
+        quote!(#[cfg(feature = "a")]).to_string()
+    );
+    assert_eq!(
+        cfg(&features(&["b", "a"])).to_string(),
+        quote!(#[cfg(all(feature = "a", feature = "b"))]).to_string()
+    );
+    assert_eq!(
+        cfg_any(&features(&["b", "a"])).to_string(),
+        quote!(#[cfg(any(feature = "a", feature = "b"))]).to_string()
+    );
+}
+
+#[test]
+fn feature_graph_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        features = true
+
+        [[include]]
+        match = ["a/**", "b/**", "c/**"]
+        bind = true
+        "#,
+        [
+            ClassBuilder::class("a/A").method("b", "()Lb/B;"),
+            ClassBuilder::class("b/B").method("a", "(La/A;)V"),
+            ClassBuilder::class("c/C").extends("a/A"),
+        ],
+    );
+    let context: Context = fixture.context();
+
+    // `a` and `b` reference each other, so `b` gates its member instead of enabling `a`
+    let graph: &FeatureGraph = context.features();
+    assert_eq!(
+        graph.manifest_table(),
+        "full = [\"a\", \"b\", \"c\"]\n\"a\" = [\"b\"]\n\"b\" = []\n\"c\" = [\"a\"]\n"
+    );
+    assert_eq!(
+        graph.implied(Some("c")),
+        BTreeSet::from(["a".to_owned(), "b".to_owned(), "c".to_owned()])
+    );
+    assert!(graph.implied(None).is_empty());
+
+    let referencing = |path: &str| BTreeSet::from([path.to_owned()]);
+    assert!(
+        context
+            .member_cfg(Id("a/A"), &referencing("b/B"))
+            .is_empty()
+    );
+    assert!(
+        context
+            .member_cfg(Id("c/C"), &referencing("b/B"))
+            .is_empty()
+    );
+    assert_eq!(
+        context
+            .member_cfg(Id("b/B"), &referencing("a/A"))
+            .to_string(),
+        quote!(#[cfg(feature = "a")]).to_string()
+    );
+
+    let b: &Class = &context.all_classes["b/B"];
+    assert!(
+        b.bindings_cfg(&context, Some(&BTreeSet::from(["b".to_owned()])))
+            .is_empty()
+    );
+    assert_eq!(
+        b.bindings_cfg(&context, None).to_string(),
+        quote!(#[cfg(feature = "b")]).to_string()
+    );
+}
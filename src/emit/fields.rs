@@ -12,6 +12,7 @@ use cafebabe::{
 };
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::BTreeSet;
 
 pub struct Field<'a> {
     pub class: &'a JavaClass,
@@ -28,6 +29,31 @@ impl<'a> Field<'a> {
         }
     }
 
+    /// JNI paths of the classes this field references through its type.
+    pub fn referenced_classes(&self) -> BTreeSet<String> {
+        referenced_class(self.java.descriptor())
+            .into_iter()
+            .collect()
+    }
+
+    /// The JNI call reading this field, with `__jni_env`, `__jni_class` and `__jni_field` in scope.
+    pub fn jni_get(&self) -> TokenStream {
+        let (get_field, this_or_class): (Ident, TokenStream) = match self.java.is_static() {
+            false => (
+                format_ident!("get_{}_field", emit_fragment_type(self.java.descriptor())),
+                quote!(self.as_raw()),
+            ),
+            true => (
+                format_ident!(
+                    "get_static_{}_field",
+                    emit_fragment_type(self.java.descriptor())
+                ),
+                quote!(__jni_class),
+            ),
+        };
+        quote!(__jni_env.#get_field(#this_or_class, __jni_field))
+    }
+
     pub fn emit(
         &self,
         context: &Context,
@@ -38,13 +64,17 @@ impl<'a> Field<'a> {
 
         let descriptor: &&FieldDescriptor<'_> = &self.java.descriptor();
 
-        let rust_set_type: TokenStream = emit_type(
-            descriptor,
-            context,
-            mod_,
-            RustTypeFlavor::ImplAsArg,
-            &mut emit_reject_reasons,
-        )?;
+        let rust_strings: bool = cc.rust_strings && is_java_string(descriptor);
+        let rust_set_type: TokenStream = match rust_strings {
+            true => quote!(impl crate::strings::IntoJavaStr),
+            false => emit_type(
+                descriptor,
+                context,
+                mod_,
+                RustTypeFlavor::ImplAsArg,
+                &mut emit_reject_reasons,
+            )?,
+        };
         let rust_get_type: TokenStream = emit_type(
             descriptor,
             context,
@@ -85,11 +115,12 @@ impl<'a> Field<'a> {
             }
         );
 
-        let attributes: TokenStream = if self.java.deprecated() {
+        let mut attributes: TokenStream = if self.java.deprecated() {
             quote!(#[deprecated])
         } else {
             quote!()
         };
+        attributes.extend(context.member_cfg(self.class.path(), &self.referenced_classes()));
 
         let mut out: TokenStream = TokenStream::new();
 
@@ -151,37 +182,88 @@ impl<'a> Field<'a> {
 
                 let java_name: Literal = cstring(self.java.name());
                 let descriptor: Literal = cstring(&self.java.descriptor().to_string());
+                let trace_span: TokenStream = context.trace_span(
+                    self.class.path().as_str(),
+                    self.java.name(),
+                    &self.java.descriptor().to_string(),
+                );
+
+                let lifetimes: TokenStream = if field_fragment == "object" {
+                    quote!('env, 'obj)
+                } else {
+                    quote!('env)
+                };
+                let value_conversion: TokenStream = match rust_strings {
+                    true => {
+                        quote!(let value = crate::strings::IntoJavaStr::into_java_str(value, __jni_env);)
+                    }
+                    false => quote!(),
+                };
 
+                let get_call: TokenStream = self.jni_get();
                 let get_docs: String = format!("**get** {docs}");
                 let set_docs: String = format!("**set** {docs}");
+                if context.config.src.macros {
+                    let (get_call, set_call): (TokenStream, TokenStream) =
+                        match self.java.is_static() {
+                            true => (
+                                quote!(static #get_field(__jni_env)),
+                                quote!(static #set_field(__jni_env)),
+                            ),
+                            false => (
+                                quote!(#get_field(__jni_env, self)),
+                                quote!(#set_field(__jni_env, self)),
+                            ),
+                        };
+                    out.extend(quote!(
+                        #[doc = #get_docs]
+                        #attributes
+                        pub fn #get<'env>(#env_param) -> #rust_get_type {
+                            #trace_span
+                            #env_let
+                            crate::macros::get_field!(#get_call, #java_name, #descriptor)
+                        }
+                    ));
+                    if !self.java.is_final() {
+                        out.extend(quote!(
+                            #[doc = #set_docs]
+                            #attributes
+                            pub fn #set<#lifetimes>(#env_param, value: #rust_set_type) {
+                                #trace_span
+                                #env_let
+                                #value_conversion
+                                crate::macros::set_field!(#set_call, #java_name, #descriptor, value)
+                            }
+                        ));
+                    }
+                    return Ok(out);
+                }
+
                 out.extend(quote!(
                     #[doc = #get_docs]
                     #attributes
                     pub fn #get<'env>(#env_param) -> #rust_get_type {
                         static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+                        #trace_span
                         #env_let
                         let __jni_class = Self::__class_global_ref(__jni_env);
                         unsafe {
                             let __jni_field = __FIELD.get_or_init(|| ::java_oxide::JFieldID::from_raw(__jni_env.#require_field(__jni_class, #java_name, #descriptor))).as_raw();
-                            __jni_env.#get_field(#this_or_class, __jni_field)
+                            #get_call
                         }
                     }
                 ));
 
                 // Setter
                 if !self.java.is_final() {
-                    let lifetimes: TokenStream = if field_fragment == "object" {
-                        quote!('env, 'obj)
-                    } else {
-                        quote!('env)
-                    };
-
                     out.extend(quote!(
                         #[doc = #set_docs]
                         #attributes
                         pub fn #set<#lifetimes>(#env_param, value: #rust_set_type) {
                             static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+                            #trace_span
                             #env_let
+                            #value_conversion
                             let __jni_class = Self::__class_global_ref(__jni_env);
                             unsafe {
                                 let __jni_field = __FIELD.get_or_init(|| ::java_oxide::JFieldID::from_raw(__jni_env.#require_field(__jni_class, #java_name, #descriptor))).as_raw();
@@ -254,6 +336,8 @@ pub enum RustTypeFlavor {
     OptionRef,
     Arg,
     Return,
+    /// `T` itself, e.g. the element type of an array.
+    Bare,
 }
 
 fn flavorify(ty: TokenStream, flavor: RustTypeFlavor) -> TokenStream {
@@ -267,6 +351,7 @@ fn flavorify(ty: TokenStream, flavor: RustTypeFlavor) -> TokenStream {
         }
         RustTypeFlavor::Arg => quote!(::java_oxide::Arg<#ty>),
         RustTypeFlavor::Return => quote!(::java_oxide::Return<'env, #ty>),
+        RustTypeFlavor::Bare => ty,
     }
 }
 
@@ -290,7 +375,7 @@ pub fn emit_type(
             FieldType::Double => quote!(f64),
             FieldType::Object(class_name) => {
                 let class: Id<'_> = Id::from(class_name);
-                if !context.all_classes.contains_key(class.as_str()) {
+                if !context.is_bound(class.as_str()) {
                     reject_reasons.push(format!(
                         "ERROR: missing class for field/argument type: {:?}",
                         class.as_str()
@@ -324,7 +409,7 @@ pub fn emit_type(
             FieldType::Object(class_name) => {
                 let class: Id<'_> = Id::from(class_name);
 
-                if !context.all_classes.contains_key(class.as_str()) {
+                if !context.is_bound(class.as_str()) {
                     reject_reasons.push(format!(
                         "ERROR: missing class for field type: {:?}",
                         class.as_str()
@@ -354,6 +439,24 @@ pub fn emit_type(
     Ok(res)
 }
 
+/// JNI path of the class `descriptor` references, or of its element class for arrays.
+pub fn referenced_class(descriptor: &FieldDescriptor) -> Option<String> {
+    match &descriptor.field_type {
+        FieldType::Object(class_name) => Some(Id::from(class_name).as_str().to_owned()),
+        _ => None,
+    }
+}
+
+/// Whether `descriptor` is a plain `java.lang.String`.
+pub fn is_java_string(descriptor: &FieldDescriptor) -> bool {
+    match &descriptor.field_type {
+        FieldType::Object(class_name) if descriptor.dimensions == 0 => {
+            Id::from(class_name).is_string_class()
+        }
+        _ => false,
+    }
+}
+
 /// Contents of {get,set}_[static_]..._field, call_..._method_a.
 pub fn emit_fragment_type(descriptor: &FieldDescriptor) -> &'static str {
     if descriptor.dimensions == 0 {
@@ -372,3 +475,13 @@ pub fn emit_fragment_type(descriptor: &FieldDescriptor) -> &'static str {
         "object"
     }
 }
+
+/// The part of the JNI function names calling a method that returns `fragment`, an [`emit_fragment_type`] or
+/// `"void"`, such as `Int` in `CallNonvirtualIntMethodA`.
+pub fn jni_function_type(fragment: &str) -> String {
+    let mut chars = fragment.chars();
+    chars
+        .next()
+        .map(|c: char| c.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
@@ -0,0 +1,155 @@
+use super::{class_proxy::ProxyMethod, classes::Class};
+use crate::{
+    emit::Context,
+    manifest::{ManifestClass, ManifestMember, ManifestProxy, ManifestSkipped},
+    parser_util::Id,
+    prelude::*,
+};
+use cafebabe::descriptors::{FieldDescriptor, FieldType};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+/// The interface `crate::futures::Completion` is proxied as, to be registered with `whenComplete`.
+pub const COMPLETION_CONSUMER: &str = "java/util/function/BiConsumer";
+
+/// Why the `_async` variants are left out when the [`COMPLETION_CONSUMER`] isn't proxied.
+pub const UNPROXIED_COMPLETION_CONSUMER: &str = "java/util/function/BiConsumer isn't proxied, add `proxy = true` to an include rule matching it";
+
+/// Whether `descriptor` is a bound `CompletableFuture` or `CompletionStage`, which can be awaited if the
+/// [`COMPLETION_CONSUMER`] is proxied, see [`has_completion_consumer`].
+pub fn is_awaitable(descriptor: &FieldDescriptor, context: &Context) -> bool {
+    let FieldType::Object(class_name) = &descriptor.field_type else {
+        return false;
+    };
+    let class: Id<'_> = Id::from(class_name);
+    descriptor.dimensions == 0
+        && matches!(
+            class.as_str(),
+            "java/util/concurrent/CompletableFuture" | "java/util/concurrent/CompletionStage"
+        )
+        && context.is_bound(class.as_str())
+}
+
+/// Whether the [`COMPLETION_CONSUMER`] is proxied, here or by the [`Context::preamble_crate`] sharing
+/// `crate::futures::Completion`.
+pub fn has_completion_consumer(context: &Context) -> bool {
+    if context.all_classes.contains_key(COMPLETION_CONSUMER) {
+        return context.config.resolve_class(COMPLETION_CONSUMER).proxy;
+    }
+    context.is_bound_with_preamble(COMPLETION_CONSUMER)
+        && context.externs[COMPLETION_CONSUMER]
+            .class
+            .proxy
+            .as_ref()
+            .is_some_and(|proxy: &ManifestProxy| proxy.emitted)
+}
+
+impl Context<'_> {
+    /// Warns once about every `_async` variant left out of the classes written so far, as the
+    /// [`COMPLETION_CONSUMER`] isn't proxied.
+    pub fn warn_unawaitable(&self) {
+        let dropped: usize = self
+            .records
+            .borrow()
+            .values()
+            .flat_map(|class: &ManifestClass| &class.members)
+            .flat_map(|member: &ManifestMember| &member.skipped)
+            .filter(|skipped: &&ManifestSkipped| skipped.reason == UNPROXIED_COMPLETION_CONSUMER)
+            .count();
+        if dropped > 0 {
+            warn!(
+                "{dropped} `_async` variants weren't emitted: {UNPROXIED_COMPLETION_CONSUMER}, or see `--report`"
+            );
+        }
+    }
+}
+
+impl Class {
+    /// Implements the proxy trait of the [`COMPLETION_CONSUMER`] for `crate::futures::Completion`, completing it from
+    /// `accept`. Default methods such as `andThen` keep their Java implementation.
+    pub fn write_completion_impl(
+        &self,
+        trait_methods: &[ProxyMethod],
+        gate: &TokenStream,
+    ) -> TokenStream {
+        let rust_proxy_name: Ident = format_ident!("{}Proxy", self.rust.struct_name);
+
+        let mut methods: TokenStream = TokenStream::new();
+        for ProxyMethod {
+            name: method_name,
+            arg_types,
+            ret,
+            cfg,
+            ..
+        } in trait_methods
+            .iter()
+            .filter(|method: &&ProxyMethod| method.is_abstract && method.name == "accept")
+        {
+            let arg_names: Vec<Ident> = (0..arg_types.len())
+                .map(|arg_idx: usize| format_ident!("arg{}", arg_idx))
+                .collect();
+            methods.extend(quote!(
+                #cfg
+                fn #method_name<'env>(&self, _env: ::java_oxide::Env<'env>, #(#arg_names: #arg_types),*) -> #ret {
+                    self.complete(#(#arg_names),*)
+                }
+            ));
+        }
+
+        quote!(
+            #gate
+            impl<T: ::java_oxide::ReferenceType> #rust_proxy_name for crate::futures::Completion<T> {
+                #methods
+            }
+        )
+    }
+}
+
+#[test]
+fn async_variant_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let classes = || {
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::class("java/lang/Throwable"),
+            ClassBuilder::class("java/util/concurrent/CompletableFuture"),
+            ClassBuilder::interface(COMPLETION_CONSUMER)
+                .method("accept", "(Ljava/lang/Object;Ljava/lang/Object;)V"),
+            ClassBuilder::class("test/World")
+                .method("load", "()Ljava/util/concurrent/CompletableFuture;"),
+        ]
+    };
+    let includes: &str = r#"
+        [[include]]
+        match = ["java/**", "test/**"]
+        bind = true
+        "#;
+
+    let fixture: Fixture = Fixture::new(includes, classes());
+    let context: Context = fixture.context();
+    let world: String = context.all_classes["test/World"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+    assert!(!world.contains("fn load_async"));
+    let manifest = context.manifest();
+    let load: &ManifestMember = manifest.classes[0]
+        .members
+        .iter()
+        .find(|member: &&ManifestMember| member.java == "load")
+        .unwrap();
+    assert_eq!(load.skipped[0].name, "load_async");
+    assert_eq!(load.skipped[0].reason, UNPROXIED_COMPLETION_CONSUMER);
+
+    let fixture: Fixture = Fixture::new(
+        &format!("{includes}\n[[include]]\nmatch = [\"{COMPLETION_CONSUMER}\"]\nproxy = true"),
+        classes(),
+    );
+    let context: Context = fixture.context();
+    let world: String = context.all_classes["test/World"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+    assert!(world.contains("fn load_async"));
+}
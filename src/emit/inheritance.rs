@@ -0,0 +1,488 @@
+use super::{
+    ExternClass,
+    classes::Class,
+    methods::{Method, MethodSignature},
+    overloads::{emit_inherited_dispatch, forward_dispatch, overload_trait_name},
+};
+use crate::{
+    config::ClassConfig,
+    emit::Context,
+    manifest::{ManifestConversions, MemberKind},
+    parser_util::{Id, signature::parse_method_descriptor},
+    prelude::*,
+};
+use cafebabe::descriptors::MethodDescriptor;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::{HashMap, HashSet};
+
+impl Class {
+    /// Emits an `as_<Super>()` upcast for every bound superclass and superinterface.
+    ///
+    /// Upcasts are named after the Rust struct of the ancestor, falling back to its fully qualified path if two
+    /// ancestors share a struct name. Names already taken by members of this class are skipped.
+    pub fn write_upcasts(
+        &self,
+        context: &Context,
+        ancestors: &[Id],
+        taken_names: &mut HashSet<String>,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+
+        // The module and struct name of every ancestor, bound here or by an `[[extern]]` crate
+        let names: Vec<(String, String)> = ancestors
+            .iter()
+            .map(|ancestor: &Id<'_>| Ok((Class::mod_for(*ancestor)?, Class::name_for(*ancestor)?)))
+            .collect::<anyhow::Result<_>>()?;
+        let mut struct_name_counts: HashMap<&str, usize> = HashMap::new();
+        for (_, struct_name) in &names {
+            *struct_name_counts.entry(struct_name.as_str()).or_insert(0) += 1;
+        }
+
+        for (ancestor, (mod_, struct_name)) in ancestors.iter().zip(&names) {
+            let upcast_name: String = if struct_name_counts[struct_name.as_str()] > 1 {
+                format!("as_{}_{}", mod_.replace("::", "_"), struct_name)
+            } else {
+                format!("as_{struct_name}")
+            };
+
+            if !taken_names.insert(upcast_name.clone()) {
+                trace!(
+                    "UPCAST SKIPPED - \"{}\" -> \"{}\": {} is already taken",
+                    self.java.path().as_str(),
+                    ancestor.as_str(),
+                    upcast_name
+                );
+                continue;
+            }
+
+            let rust_path: TokenStream = context.java_to_rust_path(*ancestor, &self.rust.mod_)?;
+            let upcast_name: Ident = format_ident!("{upcast_name}");
+            let docs: String = format!(
+                "Upcasts to `{}`",
+                ancestor.as_str().replace(['/', '$'], ".")
+            );
+
+            out.extend(quote!(
+                #[doc = #docs]
+                pub fn #upcast_name<'env>(self: &::java_oxide::Ref<'env, Self>) -> ::java_oxide::Ref<'env, #rust_path> {
+                    unsafe { ::java_oxide::Ref::from_raw(self.env(), self.as_raw()) }
+                }
+            ));
+        }
+
+        Ok(out)
+    }
+
+    /// Emits forwarding methods for the instance methods this class inherits from its bound superclasses, so they can
+    /// be called without upcasting first.
+    ///
+    /// Overridden methods are forwarded from the closest declaring class, and inherited methods whose Rust name is
+    /// already taken are skipped (they're still reachable through the matching upcast). Superclasses bound by
+    /// `[[extern]]` crates are forwarded from the descriptors and options recorded in their manifest.
+    pub fn write_inherited_methods(
+        &self,
+        context: &Context,
+        methods: &[Method],
+        taken_names: &mut HashSet<String>,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+
+        let mut overridden: HashSet<(String, String)> = methods
+            .iter()
+            .map(|m: &Method<'_>| (m.java.name().to_owned(), m.java.descriptor().to_string()))
+            .collect();
+
+        let mut super_path: Option<String> =
+            self.java.super_path().map(|s: Id| s.as_str().to_owned());
+        while let Some(path) = super_path {
+            let Some(class) = context.all_classes.get(&path) else {
+                let Some(extern_) = context.externs.get(&path) else {
+                    break;
+                };
+                super_path = extern_.class.superclass.clone();
+                out.extend(self.write_extern_inherited_methods(
+                    context,
+                    extern_,
+                    &mut overridden,
+                    taken_names,
+                )?);
+                continue;
+            };
+            super_path = class.java.super_path().map(|s: Id| s.as_str().to_owned());
+
+            let cc: ClassConfig<'_> = context.config.resolve_class(path.as_str());
+            let visible: bool = class.java.is_public() || cc.bind_private_classes;
+            let super_methods: Vec<Method<'_>> = match class.bound_members(&cc) {
+                Ok((methods, _)) => methods,
+                Err(e) => {
+                    trace!(
+                        "INHERITANCE SKIPPED - \"{}\" from \"{}\":\n{}",
+                        self.java.path().as_str(),
+                        path.as_str(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            for method in &super_methods {
+                if method.java.is_static()
+                    || method.java.is_static_init()
+                    || method.java.is_constructor()
+                    || method.java.is_private()
+                {
+                    continue;
+                }
+                let key: (String, String) = (
+                    method.java.name().to_owned(),
+                    method.java.descriptor().to_string(),
+                );
+                if !overridden.insert(key) || !visible {
+                    continue;
+                }
+                if method.is_dispatched() {
+                    // The whole overload set is forwarded at once, through its dispatching method
+                    let Some(name) = method.dispatch_name() else {
+                        continue;
+                    };
+                    if !taken_names.insert(name.clone()) {
+                        continue;
+                    }
+                    match emit_inherited_dispatch(
+                        context,
+                        &self.rust.mod_,
+                        class,
+                        &name,
+                        quote!(pub),
+                    ) {
+                        Ok(res) => out.extend(res),
+                        Err(e) => trace!(
+                            "INHERITED OVERLOADS REJECTED - \"{}.{}\" on \"{}\":\n{}",
+                            path.as_str(),
+                            name,
+                            self.java.path().as_str(),
+                            e
+                        ),
+                    }
+                    continue;
+                }
+                let Some(rust_name) = method.rust_name() else {
+                    continue;
+                };
+                if taken_names.contains(rust_name) {
+                    trace!(
+                        "INHERITED METHOD SKIPPED - \"{}.{}()\" on \"{}\": {} is already taken",
+                        path.as_str(),
+                        method.java.name(),
+                        self.java.path().as_str(),
+                        rust_name
+                    );
+                    continue;
+                }
+
+                match method.emit_inherited(context, &cc, &self.rust.mod_, class, quote!(pub)) {
+                    Ok(res) => {
+                        taken_names.insert(rust_name.to_owned());
+                        let cfg: TokenStream =
+                            context.member_cfg(self.java.path(), &method.referenced_classes());
+                        out.extend(quote!(#cfg #res));
+                    }
+                    Err(e) => trace!(
+                        "INHERITED METHOD REJECTED - \"{}.{}()\" on \"{}\":\n{}",
+                        path.as_str(),
+                        method.java.name(),
+                        self.java.path().as_str(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Emits forwarding methods for the instance methods of `declaring`, a superclass bound by an `[[extern]]` crate,
+    /// skipping those in `overridden`.
+    fn write_extern_inherited_methods(
+        &self,
+        context: &Context,
+        declaring: &ExternClass,
+        overridden: &mut HashSet<(String, String)>,
+        taken_names: &mut HashSet<String>,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+
+        let mod_: &str = &self.rust.mod_;
+        let crate_name: Ident = format_ident!("{}", declaring.crate_name);
+        let declaring_path: TokenStream =
+            context.java_to_rust_path(Id(&declaring.class.java), mod_)?;
+        let (declaring_mod, struct_name): (&str, &str) = declaring
+            .class
+            .rust
+            .rsplit_once("::")
+            .unwrap_or(("", &declaring.class.rust));
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        // The extern crate converted the types of its methods as its manifest records, through the support modules
+        // these bindings share with it (see `Context::preamble_crate`)
+        let conversions: &ManifestConversions = &declaring.class.conversions;
+        let cc: ClassConfig<'_> = ClassConfig {
+            rust_strings: conversions.rust_strings,
+            boxed_primitives: conversions.boxed_primitives,
+            optionals: conversions.optionals,
+            ..context.config.resolve_class(&declaring.class.java)
+        };
+
+        for member in &declaring.class.members {
+            // Members gated behind features of the extern crate might not exist
+            if member.kind != MemberKind::Method
+                || !overridden.insert((member.java.clone(), member.descriptor.clone()))
+                || !member.emitted
+                || !member.features.is_empty()
+            {
+                continue;
+            }
+            let Some(name) = &member.rust else {
+                continue;
+            };
+
+            if member.dispatched {
+                // The whole overload set is forwarded at once, through its dispatching method
+                if !taken_names.insert(name.clone()) {
+                    continue;
+                }
+                let mut trait_path: TokenStream = quote!(::#crate_name);
+                for ident in declaring_mod
+                    .split("::")
+                    .filter(|ident: &&str| !ident.is_empty())
+                {
+                    let ident: Ident = format_ident!("{ident}");
+                    trait_path.extend(quote!(::#ident));
+                }
+                let trait_name: Ident = format_ident!("{}", overload_trait_name(struct_name, name));
+                out.extend(forward_dispatch(
+                    &declaring_path,
+                    &quote!(#trait_path::#trait_name),
+                    struct_name,
+                    name,
+                    quote!(pub),
+                ));
+                continue;
+            }
+
+            if taken_names.contains(name) {
+                trace!(
+                    "INHERITED METHOD SKIPPED - \"{}.{}()\" on \"{}\": {} is already taken",
+                    declaring.class.java,
+                    member.java,
+                    self.java.path().as_str(),
+                    name
+                );
+                continue;
+            }
+            let signature: anyhow::Result<MethodSignature> = parse_method_descriptor(
+                &member.descriptor,
+            )
+            .and_then(|descriptor: MethodDescriptor<'static>| {
+                MethodSignature::resolve(
+                    context,
+                    &cc,
+                    mod_,
+                    member.kind,
+                    &descriptor,
+                    member.signature.as_deref(),
+                    Vec::new(),
+                )
+            });
+            let MethodSignature {
+                arg_names,
+                arg_types,
+                ret_decl,
+                ..
+            } = match signature {
+                Ok(signature) => signature,
+                Err(e) => {
+                    trace!(
+                        "INHERITED METHOD REJECTED - \"{}.{}()\" on \"{}\":\n{}",
+                        declaring.class.java,
+                        member.java,
+                        self.java.path().as_str(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            taken_names.insert(name.clone());
+
+            let docs: String = format!(
+                "{}\n\nInherited from [`{struct_name}::{name}`]({}::{name})",
+                member.java,
+                declaring_path.to_string().replace(' ', "")
+            );
+            let method_name: Ident = format_ident!("{name}");
+            out.extend(quote!(
+                #[doc = #docs]
+                pub fn #method_name<'env>(self: &::java_oxide::Ref<'env, Self>, #(#arg_names: #arg_types),*) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                    let __jni_super: ::java_oxide::Ref<'env, #declaring_path> =
+                        unsafe { ::java_oxide::Ref::from_raw(self.env(), self.as_raw()) };
+                    #declaring_path::#method_name(&__jni_super, #(#arg_names),*)
+                }
+            ));
+        }
+
+        Ok(out)
+    }
+
+    /// The name of the extension trait generated for this interface, or `None` if this isn't a public interface.
+    pub fn interface_trait_name(&self, context: &Context) -> Option<String> {
+        let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
+        if !self.java.is_interface()
+            || self.java.is_static()
+            || !(self.java.is_public() || cc.bind_private_classes)
+        {
+            return None;
+        }
+        Some(format!("{}Ext", self.rust.struct_name))
+    }
+
+    /// Emits a trait carrying the instance methods of this interface, implemented for every type that is
+    /// `AssignableTo` it, so implementors can call them without upcasting first.
+    pub fn write_interface_trait(
+        &self,
+        context: &Context,
+        gate: &TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(trait_name) = self.interface_trait_name(context) else {
+            return Ok(TokenStream::new());
+        };
+        let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
+
+        let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+        let trait_name: Ident = format_ident!("{trait_name}");
+
+        let (methods, _) = self.bound_members(&cc)?;
+
+        let mut trait_methods: TokenStream = TokenStream::new();
+        let mut dispatched: HashSet<String> = HashSet::new();
+        for method in methods
+            .iter()
+            .filter(|m: &&Method<'_>| m.java.is_public() && !m.java.is_static())
+        {
+            if method.is_dispatched() {
+                let Some(name) = method.dispatch_name() else {
+                    continue;
+                };
+                if dispatched.insert(name.clone()) {
+                    trait_methods.extend(emit_inherited_dispatch(
+                        context,
+                        &self.rust.mod_,
+                        self,
+                        &name,
+                        quote!(),
+                    )?);
+                }
+                continue;
+            }
+            match method.emit_inherited(context, &cc, &self.rust.mod_, self, quote!()) {
+                Ok(res) => {
+                    let cfg: TokenStream =
+                        context.member_cfg(self.java.path(), &method.referenced_classes());
+                    trait_methods.extend(quote!(#cfg #res));
+                }
+                Err(e) => trace!(
+                    "INTERFACE METHOD REJECTED - \"{}.{}()\":\n{}",
+                    self.java.path().as_str(),
+                    method.java.name(),
+                    e
+                ),
+            }
+        }
+
+        let docs: String = format!(
+            "Methods of [`{}`], callable on every type that implements it. Re-exported by `crate::prelude`.",
+            self.rust.struct_name
+        );
+
+        Ok(quote!(
+            #[doc = #docs]
+            #gate
+            pub trait #trait_name: ::java_oxide::AssignableTo<#rust_name> {
+                #trait_methods
+            }
+
+            #gate
+            impl<T: ::java_oxide::AssignableTo<#rust_name>> #trait_name for T {}
+        ))
+    }
+}
+
+#[test]
+fn extern_inherited_methods_test() {
+    use crate::{
+        manifest::{ManifestClass, ManifestConversions, ManifestMember},
+        parser_util::class_builder::{ClassBuilder, Fixture},
+    };
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["java/**", "test/**"]
+        bind = true
+        "#,
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::class("java/lang/String"),
+            ClassBuilder::class("test/B").extends("base/A"),
+        ],
+    );
+    let mut context: Context = fixture.context();
+    let member = |java: &str, descriptor: &str| ManifestMember {
+        java: java.to_owned(),
+        descriptor: descriptor.to_owned(),
+        signature: None,
+        rust: Some(java.to_owned()),
+        dispatched: false,
+        rust_setter: None,
+        kind: MemberKind::Method,
+        emitted: true,
+        rejected: None,
+        missing_classes: Vec::new(),
+        features: Vec::new(),
+        skipped: Vec::new(),
+    };
+    context.externs.insert(
+        "base/A".to_owned(),
+        ExternClass {
+            crate_name: "java_base".to_owned(),
+            class: ManifestClass {
+                java: "base/A".to_owned(),
+                rust: "base::A".to_owned(),
+                superclass: Some("java/lang/Object".to_owned()),
+                interfaces: Vec::new(),
+                signature: None,
+                feature: None,
+                conversions: ManifestConversions {
+                    rust_strings: true,
+                    ..Default::default()
+                },
+                proxy: None,
+                members: vec![
+                    member("greet", "(Ljava/lang/String;I)Lbase/A;"),
+                    member("missing", "()Lbase/Missing;"),
+                ],
+            },
+        },
+    );
+
+    let b: String = context.all_classes["test/B"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+    assert!(b.contains(
+        "pub fn greet < 'env > (self : & :: java_oxide :: Ref < 'env , Self > , arg0 : impl crate :: strings :: IntoJavaStr , arg1 : i32)"
+    ));
+    assert!(b.contains(
+        ":: std :: option :: Option < :: java_oxide :: Local < 'env , :: java_base :: base :: A >>"
+    ));
+    assert!(!b.contains("fn missing"));
+}
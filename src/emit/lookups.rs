@@ -0,0 +1,299 @@
+use super::{
+    classes::Class,
+    cstring,
+    fields::{Field, RustTypeFlavor, emit_type},
+    manifest::{MemberRecords, NAME_TAKEN},
+    methods::{Method, MethodSignature},
+};
+use crate::{
+    config::ClassConfig, emit::Context, identifiers::FieldMangling, parser_util::Id, prelude::*,
+};
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+
+impl Class {
+    /// Emits the fallible lookup companions of this class and its bound `methods` and `fields`: `is_available()`,
+    /// `<name>_is_available()` and `try_<name>()`, which report missing classes and members as
+    /// `crate::lookup::MissingMember` instead of panicking.
+    pub fn write_lookups(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        methods: &[Method],
+        fields: &[Field],
+        taken_names: &mut HashSet<String>,
+        records: &mut MemberRecords,
+    ) -> anyhow::Result<TokenStream> {
+        let mut out: TokenStream = TokenStream::new();
+        let object: TokenStream =
+            context.java_to_rust_path(Id("java/lang/Object"), &self.rust.mod_)?;
+        let class: Literal = cstring(self.java.path().as_str());
+
+        out.extend(quote!(
+            fn __try_class_global_ref(__jni_env: ::java_oxide::Env) -> ::std::result::Result<::java_oxide::sys::jobject, crate::lookup::MissingMember> {
+                static __CLASS: ::std::sync::OnceLock<::std::option::Option<::java_oxide::Global<#object>>> = ::std::sync::OnceLock::new();
+                crate::lookup::class(__jni_env, &__CLASS, #class)
+            }
+        ));
+        if taken_names.insert("is_available".to_owned()) {
+            out.extend(quote!(
+                /// Whether the running JVM has this class.
+                pub fn is_available(__jni_env: ::java_oxide::Env) -> bool {
+                    Self::__try_class_global_ref(__jni_env).is_ok()
+                }
+            ));
+        }
+
+        for method in methods {
+            let Some(name) = method.rust_name() else {
+                continue;
+            };
+            let name: &str = name.strip_prefix("r#").unwrap_or(name);
+            let companions: [String; 2] = [format!("{name}_is_available"), format!("try_{name}")];
+            if companions.iter().any(|c: &String| taken_names.contains(c)) {
+                trace!(
+                    "LOOKUP METHODS SKIPPED - \"{}.{}()\": {} is already taken",
+                    self.java.path().as_str(),
+                    method.java.name(),
+                    companions.join(" or ")
+                );
+                let descriptor: String = method.java.descriptor().to_string();
+                for companion in &companions {
+                    records.skipped(method.java.name(), &descriptor, companion, NAME_TAKEN);
+                }
+                continue;
+            }
+            match self.emit_method_lookups(context, cc, method, name) {
+                Ok(res) => {
+                    taken_names.extend(companions);
+                    out.extend(res);
+                }
+                Err(e) => trace!(
+                    "LOOKUP METHODS REJECTED - \"{}.{}()\":\n{}",
+                    self.java.path().as_str(),
+                    method.java.name(),
+                    e
+                ),
+            }
+        }
+
+        for field in fields {
+            let Ok(FieldMangling::GetSet(get, _)) = &field.rust_names else {
+                continue;
+            };
+            let companions: [String; 2] = [format!("{get}_is_available"), format!("try_{get}")];
+            if companions.iter().any(|c: &String| taken_names.contains(c)) {
+                trace!(
+                    "LOOKUP METHODS SKIPPED - \"{}.{}\": {} is already taken",
+                    self.java.path().as_str(),
+                    field.java.name(),
+                    companions.join(" or ")
+                );
+                let descriptor: String = field.java.descriptor().to_string();
+                for companion in &companions {
+                    records.skipped(field.java.name(), &descriptor, companion, NAME_TAKEN);
+                }
+                continue;
+            }
+            match self.emit_field_lookups(context, field, get) {
+                Ok(res) => {
+                    taken_names.extend(companions);
+                    out.extend(res);
+                }
+                Err(e) => trace!(
+                    "LOOKUP METHODS REJECTED - \"{}.{}\":\n{}",
+                    self.java.path().as_str(),
+                    field.java.name(),
+                    e
+                ),
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn emit_method_lookups(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        method: &Method,
+        name: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let mod_: &str = &self.rust.mod_;
+        let MethodSignature {
+            params_decl,
+            params_array,
+            arg_conversions,
+            ret_decl,
+            ret_conversion,
+            ret_method_fragment,
+            ..
+        } = method.signature(context, cc, mod_)?;
+
+        let is_static: bool = method.java.is_static();
+        let env_let: TokenStream = match is_static || method.java.is_constructor() {
+            true => quote!(),
+            false => quote!(let __jni_env = self.env();),
+        };
+        let linked: String = match method.is_dispatched() {
+            true => method.dispatch_name().unwrap_or_default(),
+            false => name.to_owned(),
+        };
+        let available_docs: String = format!("Whether the running JVM has [`Self::{linked}`].");
+        let try_docs: String = format!(
+            "Variant of [`Self::{linked}`] failing with [`MissingMember`](crate::lookup::MissingMember) instead of panicking if the running JVM lacks it."
+        );
+
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let lookup: Ident = format_ident!("__lookup_{name}");
+        let is_available: Ident = format_ident!("{name}_is_available");
+        let try_name: Ident = format_ident!("try_{name}");
+        let class: Literal = cstring(self.java.path().as_str());
+        let java_name: Literal = cstring(method.java.name());
+        let descriptor: Literal = cstring(&method.java.descriptor().to_string());
+        let cfg: TokenStream = context.member_cfg(self.java.path(), &method.referenced_classes());
+        let trace_span: TokenStream = method.trace_span(context);
+        // Called through the ID resolved by the lookup, rather than the cache of the panicking method
+        let call: TokenStream = method.jni_call(
+            &arg_conversions,
+            &params_array,
+            &ret_conversion,
+            ret_method_fragment,
+            quote!(Self::__try_class_global_ref(__jni_env)?),
+            quote!(Self::#lookup(__jni_env, __jni_class)?),
+        );
+
+        Ok(quote!(
+            #cfg
+            fn #lookup(__jni_env: ::java_oxide::Env, __jni_class: ::java_oxide::sys::jobject) -> ::std::result::Result<::java_oxide::sys::jmethodID, crate::lookup::MissingMember> {
+                static __METHOD: ::std::sync::OnceLock<::std::option::Option<::java_oxide::JMethodID>> = ::std::sync::OnceLock::new();
+                crate::lookup::method(__jni_env, &__METHOD, __jni_class, #class, #java_name, #descriptor, #is_static)
+            }
+
+            #[doc = #available_docs]
+            #cfg
+            pub fn #is_available(__jni_env: ::java_oxide::Env) -> bool {
+                Self::__try_class_global_ref(__jni_env)
+                    .and_then(|__jni_class| Self::#lookup(__jni_env, __jni_class))
+                    .is_ok()
+            }
+
+            #[doc = #try_docs]
+            #cfg
+            pub fn #try_name<'env>(#params_decl) -> ::std::result::Result<::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>>, crate::lookup::MissingMember> {
+                #trace_span
+                #env_let
+                ::std::result::Result::Ok(#call)
+            }
+        ))
+    }
+
+    fn emit_field_lookups(
+        &self,
+        context: &Context,
+        field: &Field,
+        get: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let mut reject_reasons: Vec<String> = Vec::new();
+        let get_type: TokenStream = emit_type(
+            field.java.descriptor(),
+            context,
+            &self.rust.mod_,
+            RustTypeFlavor::OptionLocal,
+            &mut reject_reasons,
+        )?;
+        if !reject_reasons.is_empty() {
+            return Err(anyhow::anyhow!(reject_reasons.join("\n")));
+        }
+
+        let is_static: bool = field.java.is_static();
+        let (env_param, env_let): (TokenStream, TokenStream) = match is_static {
+            true => (quote!(__jni_env: ::java_oxide::Env<'env>), quote!()),
+            false => (
+                quote!(self: &::java_oxide::Ref<'env, Self>),
+                quote!(let __jni_env = self.env();),
+            ),
+        };
+        let available_docs: String =
+            format!("Whether the running JVM has the field behind [`Self::{get}`].");
+        let try_docs: String = format!(
+            "Variant of [`Self::{get}`] failing with [`MissingMember`](crate::lookup::MissingMember) instead of panicking if the running JVM lacks the field."
+        );
+
+        let lookup: Ident = format_ident!("__lookup_{get}");
+        let is_available: Ident = format_ident!("{get}_is_available");
+        let try_get: Ident = format_ident!("try_{get}");
+        let class: Literal = cstring(self.java.path().as_str());
+        let java_name: Literal = cstring(field.java.name());
+        let descriptor: Literal = cstring(&field.java.descriptor().to_string());
+        let cfg: TokenStream = context.member_cfg(self.java.path(), &field.referenced_classes());
+        let trace_span: TokenStream = context.trace_span(
+            self.java.path().as_str(),
+            field.java.name(),
+            &field.java.descriptor().to_string(),
+        );
+        // Read through the ID resolved by the lookup, rather than the cache of the panicking getter
+        let get_call: TokenStream = field.jni_get();
+
+        Ok(quote!(
+            #cfg
+            fn #lookup(__jni_env: ::java_oxide::Env, __jni_class: ::java_oxide::sys::jobject) -> ::std::result::Result<::java_oxide::sys::jfieldID, crate::lookup::MissingMember> {
+                static __FIELD: ::std::sync::OnceLock<::std::option::Option<::java_oxide::JFieldID>> = ::std::sync::OnceLock::new();
+                crate::lookup::field(__jni_env, &__FIELD, __jni_class, #class, #java_name, #descriptor, #is_static)
+            }
+
+            #[doc = #available_docs]
+            #cfg
+            pub fn #is_available(__jni_env: ::java_oxide::Env) -> bool {
+                Self::__try_class_global_ref(__jni_env)
+                    .and_then(|__jni_class| Self::#lookup(__jni_env, __jni_class))
+                    .is_ok()
+            }
+
+            #[doc = #try_docs]
+            #cfg
+            pub fn #try_get<'env>(#env_param) -> ::std::result::Result<#get_type, crate::lookup::MissingMember> {
+                #trace_span
+                #env_let
+                let __jni_class = Self::__try_class_global_ref(__jni_env)?;
+                let __jni_field = Self::#lookup(__jni_env, __jni_class)?;
+                ::std::result::Result::Ok(unsafe { #get_call })
+            }
+        ))
+    }
+}
+
+#[test]
+fn lookups_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["**"]
+        bind = true
+        fallible-lookups = true
+        "#,
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::class("java/lang/Throwable"),
+            ClassBuilder::class("test/Player").concrete_method("getId", "()I"),
+        ],
+    );
+    let context: Context = fixture.context();
+    let player: String = context.all_classes["test/Player"]
+        .write(&context, None)
+        .unwrap()
+        .to_string();
+
+    // `try_getId` calls through the ID its lookup cached, instead of looking it up again through `getId`
+    let start: usize = player.find("pub fn try_getId").unwrap();
+    let end: usize = player[start + 1..]
+        .find("pub fn")
+        .map_or(player.len(), |end: usize| start + 1 + end);
+    let try_get_id: &str = &player[start..end];
+    assert!(try_get_id.contains("Self :: __lookup_getId (__jni_env , __jni_class) ?"));
+    assert!(try_get_id.contains("call_int_method_a"));
+    assert!(!try_get_id.contains("Self :: getId ("));
+}
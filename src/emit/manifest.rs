@@ -0,0 +1,210 @@
+use super::{Context, classes::Class, fields::Field, methods::Method};
+use crate::{
+    config::ClassConfig,
+    identifiers::FieldMangling,
+    manifest::{
+        Manifest, ManifestClass, ManifestConversions, ManifestMember, ManifestProxy,
+        ManifestSkipped, MemberKind,
+    },
+    parser_util::Id,
+};
+use cafebabe::descriptors::ClassName;
+use proc_macro2::TokenStream;
+use std::collections::BTreeSet;
+
+/// Why a variant or lookup companion whose name another item took wasn't emitted.
+pub const NAME_TAKEN: &str = "the name is already taken";
+
+impl Context<'_> {
+    /// Describes every class written so far and its members, see [`Manifest`].
+    pub fn manifest(&self) -> Manifest {
+        Manifest {
+            classes: self.records.borrow().values().cloned().collect(),
+        }
+    }
+
+    /// JNI paths of the classes in `referenced` which aren't bound.
+    fn unbound_classes(&self, referenced: &BTreeSet<String>) -> Vec<String> {
+        referenced
+            .iter()
+            .filter(|path: &&String| !self.is_bound(path))
+            .cloned()
+            .collect()
+    }
+}
+
+/// What [`Class::write`] emitted of the members of a class, recorded as it goes for the manifest and the report.
+#[derive(Default)]
+pub struct MemberRecords(Vec<ManifestMember>);
+
+impl MemberRecords {
+    /// Records the `result` of emitting `method`.
+    pub fn method(
+        &mut self,
+        context: &Context,
+        method: &Method,
+        result: &anyhow::Result<TokenStream>,
+    ) {
+        if method.java.is_static_init() {
+            return;
+        }
+        let kind: MemberKind = method.kind();
+        let (rejected, missing_classes, features) = outcome(
+            context,
+            method.class.path(),
+            result,
+            &method.referenced_classes(),
+        );
+        self.0.push(ManifestMember {
+            java: method.java.name().to_owned(),
+            descriptor: method.java.descriptor().to_string(),
+            signature: method.java.signature().map(str::to_owned),
+            rust: match method.is_dispatched() {
+                true => method.dispatch_name(),
+                false => method.rust_name().map(str::to_owned),
+            },
+            dispatched: method.is_dispatched(),
+            rust_setter: None,
+            kind,
+            emitted: result.is_ok(),
+            rejected,
+            missing_classes,
+            features,
+            skipped: Vec::new(),
+        });
+    }
+
+    /// Records the `result` of emitting `field`.
+    pub fn field(
+        &mut self,
+        context: &Context,
+        field: &Field,
+        result: &anyhow::Result<TokenStream>,
+    ) {
+        let kind: MemberKind = match (&field.rust_names, field.java.is_static()) {
+            (Ok(FieldMangling::ConstValue(..)), _) => MemberKind::Constant,
+            (_, true) => MemberKind::StaticField,
+            (_, false) => MemberKind::Field,
+        };
+        let (rust, rust_setter): (Option<String>, Option<String>) = match &field.rust_names {
+            Ok(FieldMangling::ConstValue(name, _)) => (Some(name.clone()), None),
+            Ok(FieldMangling::GetSet(get, set)) => (
+                Some(get.clone()),
+                (!field.java.is_final()).then(|| set.clone()),
+            ),
+            Err(_) => (None, None),
+        };
+        let (rejected, missing_classes, features) = outcome(
+            context,
+            field.class.path(),
+            result,
+            &field.referenced_classes(),
+        );
+        self.0.push(ManifestMember {
+            java: field.java.name().to_owned(),
+            descriptor: field.java.descriptor().to_string(),
+            signature: field.java.signature().map(str::to_owned),
+            rust,
+            dispatched: false,
+            rust_setter,
+            kind,
+            emitted: result.is_ok(),
+            rejected,
+            missing_classes,
+            features,
+            skipped: Vec::new(),
+        });
+    }
+
+    /// Records that `name`, a variant or lookup companion of the member `java` with `descriptor`, wasn't emitted for
+    /// `reason`.
+    pub fn skipped(&mut self, java: &str, descriptor: &str, name: &str, reason: &str) {
+        if let Some(member) = self
+            .0
+            .iter_mut()
+            .find(|m: &&mut ManifestMember| m.java == java && m.descriptor == descriptor)
+        {
+            member.skipped.push(ManifestSkipped {
+                name: name.to_owned(),
+                reason: reason.to_owned(),
+            });
+        }
+    }
+}
+
+/// Why the member of `class` referencing `referenced` was rejected and the unbound classes it references, or the
+/// features gating it if it was emitted as `result`.
+fn outcome(
+    context: &Context,
+    class: Id,
+    result: &anyhow::Result<TokenStream>,
+    referenced: &BTreeSet<String>,
+) -> (Option<String>, Vec<String>, Vec<String>) {
+    match result {
+        Ok(_) => (
+            None,
+            Vec::new(),
+            context
+                .member_features(class, referenced)
+                .into_iter()
+                .collect(),
+        ),
+        Err(e) => (
+            Some(e.to_string()),
+            context.unbound_classes(referenced),
+            Vec::new(),
+        ),
+    }
+}
+
+impl Class {
+    /// Records what [`Self::write`] emitted of this class, for [`Context::manifest`] and [`Context::report`].
+    pub fn record(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        members: MemberRecords,
+        proxy: Option<(&anyhow::Result<TokenStream>, &[Method])>,
+    ) {
+        let proxy: Option<ManifestProxy> = proxy.map(|(result, methods)| ManifestProxy {
+            emitted: result.is_ok(),
+            rejected: result.as_ref().err().map(|e| e.to_string()),
+            missing_classes: match result {
+                Ok(_) => Vec::new(),
+                Err(_) => context.unbound_classes(
+                    &methods
+                        .iter()
+                        .filter(|method: &&Method<'_>| method.is_proxied())
+                        .flat_map(Method::referenced_classes)
+                        .collect(),
+                ),
+            },
+        });
+        let class: ManifestClass = ManifestClass {
+            java: self.java.path().as_str().to_owned(),
+            rust: match self.rust.mod_.is_empty() {
+                true => self.rust.struct_name.clone(),
+                false => format!("{}::{}", self.rust.mod_, self.rust.struct_name),
+            },
+            superclass: self.java.super_path().map(|s: Id| s.as_str().to_owned()),
+            interfaces: self
+                .java
+                .interfaces()
+                .map(|i: &ClassName<'_>| i.to_string())
+                .collect(),
+            signature: self.java.signature().map(str::to_owned),
+            feature: context.feature_of(self.java.path().as_str()),
+            conversions: ManifestConversions {
+                rust_strings: cc.rust_strings,
+                boxed_primitives: cc.boxed_primitives,
+                optionals: cc.optionals,
+            },
+            proxy,
+            members: members.0,
+        };
+        context
+            .records
+            .borrow_mut()
+            .insert(class.java.clone(), class);
+    }
+}
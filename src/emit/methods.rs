@@ -1,23 +1,227 @@
 use super::{
+    boxed::boxed_primitive,
+    classes::Class,
     cstring,
-    fields::{RustTypeFlavor, emit_fragment_type, emit_type},
+    fields::{
+        RustTypeFlavor, emit_fragment_type, emit_type, is_java_string, jni_function_type,
+        referenced_class,
+    },
+    futures::{COMPLETION_CONSUMER, is_awaitable},
     known_docs_url::KnownDocsUrl,
+    optionals::{OptionalKind, generic_element, optional_kind},
 };
 use crate::{
     config::ClassConfig,
     emit::Context,
     identifiers::MethodManglingStyle,
-    parser_util::{JavaClass, JavaMethod},
+    manifest::MemberKind,
+    parser_util::{
+        Id, JavaClass, JavaMethod,
+        signature::{MethodTypeSignature, parse_method_signature},
+    },
 };
-use cafebabe::descriptors::{MethodDescriptor, ReturnDescriptor};
+use cafebabe::descriptors::{FieldDescriptor, FieldType, MethodDescriptor, ReturnDescriptor};
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::BTreeSet;
+
+/// The Rust-facing parameters and return type of a bound Java method.
+pub struct MethodSignature {
+    /// Contents of `fn name<'env>(...)`, including the `self`/`__jni_env` receiver.
+    pub params_decl: TokenStream,
+    /// Contents of `let __jni_args = [...];`
+    pub params_array: TokenStream,
+    /// Statements converting arguments before `__jni_args` is built, with `__jni_env` in scope.
+    pub arg_conversions: TokenStream,
+    /// Names of the Java arguments, in declaration order.
+    pub arg_names: Vec<Ident>,
+    /// Rust types of the Java arguments, matching `arg_names`.
+    pub arg_types: Vec<TokenStream>,
+    pub ret_decl: TokenStream,
+    /// Appended to the `Result` of the JNI call to convert it into `ret_decl`, e.g. to unbox primitives.
+    pub ret_conversion: TokenStream,
+    /// Contents of `call_..._method_a`.
+    pub ret_method_fragment: &'static str,
+}
+
+impl MethodSignature {
+    /// Resolves the Rust parameter and return types of a method of `kind` with `descriptor` and the generic
+    /// `signature`, as configured by `cc`.
+    ///
+    /// Fails with `emit_reject_reasons` and every other reason the method can't be bound from `mod_`.
+    pub fn resolve(
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+        kind: MemberKind,
+        descriptor: &MethodDescriptor,
+        signature: Option<&str>,
+        mut emit_reject_reasons: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        // Parameter names may or may not be available as extra debug information.  Example:
+        // https://docs.oracle.com/javase/tutorial/reflect/member/methodparameterreflection.html
+
+        let mut params_array: TokenStream = TokenStream::new(); // Contents of let __jni_args = [...];
+        let mut arg_names: Vec<Ident> = Vec::new();
+        let mut arg_types: Vec<TokenStream> = Vec::new();
+        let mut arg_conversions: TokenStream = TokenStream::new();
+
+        // Contents of fn name<'env>(...) {
+        let mut params_decl: TokenStream = if kind != MemberKind::Method {
+            quote!(__jni_env: ::java_oxide::Env<'env>,)
+        } else {
+            quote!(self: &::java_oxide::Ref<'env, Self>,)
+        };
+
+        let generic: Option<MethodTypeSignature> = match cc.optionals {
+            true => generic_signature(signature, descriptor),
+            false => None,
+        };
+
+        for (arg_idx, arg) in descriptor.parameters.iter().enumerate() {
+            let arg_name: Ident = format_ident!("arg{}", arg_idx);
+            let arg_type: TokenStream = if cc.rust_strings && is_java_string(arg) {
+                arg_conversions.extend(quote!(
+                    let #arg_name = crate::strings::IntoJavaStr::into_java_str(#arg_name, __jni_env);
+                ));
+                quote!(impl crate::strings::IntoJavaStr)
+            } else if cc.boxed_primitives && boxed_primitive(arg, context).is_some() {
+                let wrapper: TokenStream = emit_type(
+                    arg,
+                    context,
+                    mod_,
+                    RustTypeFlavor::Bare,
+                    &mut emit_reject_reasons,
+                )?;
+                arg_conversions.extend(quote!(
+                    let #arg_name = crate::boxed::IntoBoxed::<#wrapper>::into_boxed(#arg_name, __jni_env)?;
+                ));
+                quote!(impl crate::boxed::IntoBoxed<#wrapper>)
+            } else if cc.optionals
+                && let Some(kind) = optional_kind(arg, context)
+            {
+                let optional: TokenStream = emit_type(
+                    arg,
+                    context,
+                    mod_,
+                    RustTypeFlavor::Bare,
+                    &mut emit_reject_reasons,
+                )?;
+                let element: TokenStream = generic_element(
+                    generic
+                        .as_ref()
+                        .map(|g: &MethodTypeSignature| &g.params[arg_idx]),
+                    context,
+                    mod_,
+                )?;
+                let into_optional: TokenStream = kind.into_optional(&optional, &element);
+                arg_conversions.extend(quote!(
+                    let #arg_name = <_ as #into_optional>::into_optional(#arg_name, __jni_env)?;
+                ));
+                quote!(impl #into_optional)
+            } else {
+                emit_type(
+                    arg,
+                    context,
+                    mod_,
+                    RustTypeFlavor::ImplAsArg,
+                    &mut emit_reject_reasons,
+                )?
+            };
+
+            params_array.extend(quote!(::java_oxide::AsJValue::as_jvalue(&#arg_name),));
+            params_decl.extend(quote!(#arg_name: #arg_type,));
+            arg_names.push(arg_name);
+            arg_types.push(arg_type);
+        }
+
+        let mut ret_conversion: TokenStream = TokenStream::new();
+        let mut ret_decl: TokenStream = if let ReturnDescriptor::Return(desc) =
+            &descriptor.return_type
+            && cc.boxed_primitives
+            && let Some(primitive) = boxed_primitive(desc, context)
+        {
+            let wrapper: TokenStream = emit_type(
+                desc,
+                context,
+                mod_,
+                RustTypeFlavor::Bare,
+                &mut emit_reject_reasons,
+            )?;
+            ret_conversion = quote!(.and_then(|__jni_value: ::std::option::Option<::java_oxide::Local<'env, #wrapper>>| {
+                __jni_value
+                    .map(|__jni_value| <#wrapper as crate::boxed::Boxed>::unbox_value(&__jni_value))
+                    .transpose()
+            }));
+            emit_type(
+                &primitive,
+                context,
+                mod_,
+                RustTypeFlavor::Bare,
+                &mut emit_reject_reasons,
+            )
+            .map(|primitive: TokenStream| quote!(::std::option::Option<#primitive>))?
+        } else if let ReturnDescriptor::Return(desc) = &descriptor.return_type {
+            emit_type(
+                desc,
+                context,
+                mod_,
+                RustTypeFlavor::OptionLocal,
+                &mut emit_reject_reasons,
+            )?
+        } else {
+            quote!(())
+        };
+
+        let mut ret_method_fragment: &'static str =
+            if let ReturnDescriptor::Return(desc) = &descriptor.return_type {
+                emit_fragment_type(desc)
+            } else {
+                "void"
+            };
+
+        if kind == MemberKind::Constructor {
+            if descriptor.return_type == ReturnDescriptor::Void {
+                ret_method_fragment = "object";
+                ret_decl = quote!(::java_oxide::Local<'env, Self>);
+            } else {
+                emit_reject_reasons.push("ERROR: Constructor should've returned void".to_string());
+            }
+        }
+
+        if !emit_reject_reasons.is_empty() {
+            return Err(anyhow::anyhow!(emit_reject_reasons.join("\n")));
+        }
+
+        Ok(Self {
+            params_decl,
+            params_array,
+            arg_conversions,
+            arg_names,
+            arg_types,
+            ret_decl,
+            ret_conversion,
+            ret_method_fragment,
+        })
+    }
+}
+
+/// Parses the generic `signature` of a method with `descriptor`, if it has one matching the descriptor.
+fn generic_signature(
+    signature: Option<&str>,
+    descriptor: &MethodDescriptor,
+) -> Option<MethodTypeSignature> {
+    let signature: MethodTypeSignature = parse_method_signature(signature?).ok()?;
+    // Signatures omit synthetic parameters, such as the outer instance of inner class constructors
+    (signature.params.len() == descriptor.parameters.len()).then_some(signature)
+}
 
 pub struct Method<'a> {
     pub class: &'a JavaClass,
     pub java: JavaMethod<'a>,
     rust_name: Option<String>,
     mangling_style: MethodManglingStyle,
+    dispatched: bool,
 }
 
 impl<'a> Method<'a> {
@@ -27,6 +231,7 @@ impl<'a> Method<'a> {
             java: JavaMethod::from(java),
             rust_name: None,
             mangling_style: MethodManglingStyle::Java,
+            dispatched: false,
         };
         result.set_mangling_style(MethodManglingStyle::Java);
         result
@@ -36,6 +241,10 @@ impl<'a> Method<'a> {
         self.rust_name.as_deref()
     }
 
+    pub fn mangling_style(&self) -> MethodManglingStyle {
+        self.mangling_style
+    }
+
     pub fn set_mangling_style(&mut self, style: MethodManglingStyle) {
         self.mangling_style = style;
         self.rust_name = self
@@ -44,23 +253,91 @@ impl<'a> Method<'a> {
             .ok()
     }
 
-    pub fn emit(
+    /// Whether this method is an overload bound behind a dispatching method, see [`OverloadStyle::Dispatch`].
+    ///
+    /// [`OverloadStyle::Dispatch`]: crate::config::OverloadStyle::Dispatch
+    pub fn is_dispatched(&self) -> bool {
+        self.dispatched
+    }
+
+    /// Hides this overload behind a dispatching method.
+    pub fn set_dispatched(&mut self) {
+        self.dispatched = true;
+    }
+
+    /// The name of the generated Rust method, which is the mangled name with a `__` prefix for dispatched overloads.
+    pub fn emitted_name(&self) -> Option<String> {
+        let name: &str = self.rust_name()?;
+        match self.dispatched {
+            true => Some(format!("__{}", name.strip_prefix("r#").unwrap_or(name))),
+            false => Some(name.to_owned()),
+        }
+    }
+
+    /// The name of the method dispatching to this overload.
+    pub fn dispatch_name(&self) -> Option<String> {
+        MethodManglingStyle::Java
+            .mangle(self.java.name(), self.java.descriptor())
+            .ok()
+    }
+
+    /// Enters the `tracing` span of a call to this method, see [`Context::trace_span`].
+    pub fn trace_span(&self, context: &Context) -> TokenStream {
+        context.trace_span(
+            self.class.path().as_str(),
+            self.java.name(),
+            &self.java.descriptor().to_string(),
+        )
+    }
+
+    /// Whether this method is a constructor, a static method or an instance method.
+    pub fn kind(&self) -> MemberKind {
+        if self.java.is_constructor() {
+            MemberKind::Constructor
+        } else if self.java.is_static() {
+            MemberKind::StaticMethod
+        } else {
+            MemberKind::Method
+        }
+    }
+
+    /// The generic `Signature` of this method, if it has one matching its descriptor.
+    fn generic_signature(&self) -> Option<MethodTypeSignature> {
+        generic_signature(self.java.signature(), self.java.descriptor())
+    }
+
+    /// JNI paths of the classes this method references, through its descriptor and generic signature.
+    pub fn referenced_classes(&self) -> BTreeSet<String> {
+        let descriptor: &MethodDescriptor<'_> = self.java.descriptor();
+        let returned: Option<&FieldDescriptor> = match &descriptor.return_type {
+            ReturnDescriptor::Return(desc) => Some(desc),
+            ReturnDescriptor::Void => None,
+        };
+        let mut classes: BTreeSet<String> = descriptor
+            .parameters
+            .iter()
+            .chain(returned)
+            .filter_map(referenced_class)
+            .collect();
+        if let Some(signature) = self.generic_signature() {
+            for ty in signature.params.iter().chain(&signature.ret) {
+                ty.collect_classes(&mut classes);
+            }
+        }
+        classes
+    }
+
+    /// Resolves the Rust parameter and return types of this method, as configured by `cc`.
+    ///
+    /// Fails with every reason the method can't be bound from `mod_`.
+    pub fn signature(
         &self,
         context: &Context,
         cc: &ClassConfig,
         mod_: &str,
-    ) -> anyhow::Result<TokenStream> {
+    ) -> anyhow::Result<MethodSignature> {
         let mut emit_reject_reasons: Vec<String> = Vec::new();
 
-        let descriptor: &MethodDescriptor<'_> = self.java.descriptor();
-
-        let method_name: String = if let Some(name) = self.rust_name() {
-            name.to_owned()
-        } else {
-            emit_reject_reasons.push("ERROR: Failed to mangle method name".to_string());
-            self.java.name().to_owned()
-        };
-
         if self.java.is_bridge() {
             emit_reject_reasons.push("Bridge method - type erasure".to_string());
         }
@@ -69,72 +346,61 @@ impl<'a> Method<'a> {
                 .push("Static class constructor - never needs to be called by Rust.".to_string());
         }
 
-        // Parameter names may or may not be available as extra debug information.  Example:
-        // https://docs.oracle.com/javase/tutorial/reflect/member/methodparameterreflection.html
+        MethodSignature::resolve(
+            context,
+            cc,
+            mod_,
+            self.kind(),
+            self.java.descriptor(),
+            self.java.signature(),
+            emit_reject_reasons,
+        )
+    }
 
-        let mut params_array: TokenStream = TokenStream::new(); // Contents of let __jni_args = [...];
+    pub fn emit(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let mut emit_reject_reasons: Vec<String> = Vec::new();
 
-        // Contents of fn name<'env>(...) {
-        let mut params_decl: TokenStream = if self.java.is_constructor() || self.java.is_static() {
-            quote!(__jni_env: ::java_oxide::Env<'env>,)
+        let method_name: String = if let Some(name) = self.emitted_name() {
+            name
         } else {
-            quote!(self: &::java_oxide::Ref<'env, Self>,)
+            emit_reject_reasons.push("ERROR: Failed to mangle method name".to_string());
+            self.java.name().to_owned()
         };
 
-        for (arg_idx, arg) in descriptor.parameters.iter().enumerate() {
-            let arg_name: Ident = format_ident!("arg{}", arg_idx);
-            let arg_type: TokenStream = emit_type(
-                arg,
-                context,
-                mod_,
-                RustTypeFlavor::ImplAsArg,
-                &mut emit_reject_reasons,
-            )?;
-
-            params_array.extend(quote!(::java_oxide::AsJValue::as_jvalue(&#arg_name),));
-            params_decl.extend(quote!(#arg_name: #arg_type,));
-        }
-
-        let mut ret_decl: TokenStream =
-            if let ReturnDescriptor::Return(desc) = &descriptor.return_type {
-                emit_type(
-                    desc,
-                    context,
-                    mod_,
-                    RustTypeFlavor::OptionLocal,
-                    &mut emit_reject_reasons,
-                )?
-            } else {
-                quote!(())
-            };
-
-        let mut ret_method_fragment: &str =
-            if let ReturnDescriptor::Return(desc) = &descriptor.return_type {
-                emit_fragment_type(desc)
-            } else {
-                "void"
-            };
-
-        if self.java.is_constructor() {
-            if descriptor.return_type == ReturnDescriptor::Void {
-                ret_method_fragment = "object";
-                ret_decl = quote!(::java_oxide::Local<'env, Self>);
-            } else {
-                emit_reject_reasons.push("ERROR: Constructor should've returned void".to_string());
+        let signature: MethodSignature = match self.signature(context, cc, mod_) {
+            Ok(signature) if emit_reject_reasons.is_empty() => signature,
+            Ok(_) => return Err(anyhow::anyhow!(emit_reject_reasons.join("\n"))),
+            Err(e) => {
+                emit_reject_reasons.push(e.to_string());
+                return Err(anyhow::anyhow!(emit_reject_reasons.join("\n")));
             }
-        }
-
-        if !emit_reject_reasons.is_empty() {
-            return Err(anyhow::anyhow!(emit_reject_reasons.join("\n")));
-        }
+        };
+        let MethodSignature {
+            params_decl,
+            params_array,
+            arg_conversions,
+            arg_names,
+            ret_decl,
+            ret_conversion,
+            ret_method_fragment,
+            ..
+        } = signature;
 
         let mut out: TokenStream = TokenStream::new();
 
-        let attributes: TokenStream = if self.java.deprecated() {
+        let mut attributes: TokenStream = if self.java.deprecated() {
             quote!(#[deprecated])
         } else {
             quote!()
         };
+        if self.dispatched {
+            attributes.extend(quote!(#[doc(hidden)]));
+        }
 
         let docs: String = match KnownDocsUrl::from_method(cc, self) {
             Some(url) => format!("{url}"),
@@ -155,7 +421,66 @@ impl<'a> Method<'a> {
         let java_name: Literal = cstring(self.java.name());
         let descriptor: Literal = cstring(&self.java.descriptor().to_string());
         let method_name: Ident = format_ident!("{method_name}");
+        let trace_span: TokenStream = self.trace_span(context);
 
+        if context.config.src.macros {
+            let call: TokenStream = if self.java.is_constructor() {
+                quote!(new(__jni_env))
+            } else if self.java.is_static() {
+                let call: Ident = format_ident!("call_static_{ret_method_fragment}_method_a");
+                quote!(static #call(__jni_env))
+            } else {
+                let call: Ident = format_ident!("call_{ret_method_fragment}_method_a");
+                quote!(#call(__jni_env, self))
+            };
+            out.extend(quote!(
+                #[doc = #docs]
+                #attributes
+                pub fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                    #trace_span
+                    #env_let
+                    #arg_conversions
+                    crate::macros::call_method!(#call, #java_name, #descriptor, [#(#arg_names),*]) #ret_conversion
+                }
+            ));
+            return Ok(out);
+        }
+
+        let call: TokenStream = self.jni_call(
+            &arg_conversions,
+            &params_array,
+            &ret_conversion,
+            ret_method_fragment,
+            quote!(Self::__class_global_ref(__jni_env)),
+            quote!(__METHOD.get_or_init(||
+                ::java_oxide::JMethodID::from_raw(__jni_env.#require_method(__jni_class, #java_name, #descriptor))
+            ).as_raw()),
+        );
+        out.extend(quote!(
+            #[doc = #docs]
+            #attributes
+            pub fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                #trace_span
+                #env_let
+                #call
+            }
+        ));
+
+        Ok(out)
+    }
+
+    /// The `unsafe` block converting the arguments and calling this method, with `__jni_env` in scope. `class` and
+    /// `method` are the expressions resolving the `jclass` and `jmethodID` to call through.
+    pub fn jni_call(
+        &self,
+        arg_conversions: &TokenStream,
+        params_array: &TokenStream,
+        ret_conversion: &TokenStream,
+        ret_method_fragment: &str,
+        class: TokenStream,
+        method: TokenStream,
+    ) -> TokenStream {
         let call: TokenStream = if self.java.is_constructor() {
             quote!(__jni_env.new_object_a(__jni_class, __jni_method, __jni_args.as_ptr()))
         } else if self.java.is_static() {
@@ -165,25 +490,622 @@ impl<'a> Method<'a> {
             let call: Ident = format_ident!("call_{ret_method_fragment}_method_a");
             quote!(    __jni_env.#call(self.as_raw(), __jni_method, __jni_args.as_ptr()))
         };
+        quote!(
+            unsafe {
+                #arg_conversions
+                let __jni_args = [#params_array];
+                let __jni_class = #class;
+                let __jni_method = #method;
 
-        out.extend(quote!(
+                #call #ret_conversion
+            }
+        )
+    }
+
+    /// The name of the `_string()` variant of this method, if it returns a `java.lang.String`.
+    pub fn string_variant_name(&self, cc: &ClassConfig) -> Option<String> {
+        match &self.java.descriptor().return_type {
+            ReturnDescriptor::Return(desc)
+                if cc.rust_strings && is_java_string(desc) && !self.java.is_constructor() =>
+            {
+                let name: &str = self.rust_name()?;
+                Some(format!(
+                    "{}_string",
+                    name.strip_prefix("r#").unwrap_or(name)
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Emits a variant of this String-returning method that converts the result into a Rust `String`.
+    pub fn emit_string_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(method_name) = self.string_variant_name(cc) else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        self.emit_converting_variant(
+            context,
+            cc,
+            mod_,
+            &method_name,
+            quote!(::std::option::Option<::std::string::String>),
+            |result: TokenStream| {
+                quote!(::std::result::Result::Ok(#result?.map(|string| string.to_string_lossy())))
+            },
+            "returning a Rust `String`. Invalid UTF-16 is replaced with `U+FFFD`",
+        )
+    }
+
+    /// The name of the `_option()` variant of this method, if it returns a bound `java.util.Optional*`.
+    pub fn option_variant_name(&self, context: &Context, cc: &ClassConfig) -> Option<String> {
+        match &self.java.descriptor().return_type {
+            ReturnDescriptor::Return(desc)
+                if cc.optionals
+                    && optional_kind(desc, context).is_some()
+                    && !self.java.is_constructor() =>
+            {
+                let name: &str = self.rust_name()?;
+                Some(format!(
+                    "{}_option",
+                    name.strip_prefix("r#").unwrap_or(name)
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Emits a variant of this Optional-returning method that unwraps the result into a Rust `Option`.
+    pub fn emit_option_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let (Some(method_name), ReturnDescriptor::Return(desc)) = (
+            self.option_variant_name(context, cc),
+            &self.java.descriptor().return_type,
+        ) else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let kind: OptionalKind = optional_kind(desc, context)
+            .ok_or_else(|| anyhow::anyhow!("ERROR: Not an Optional return type"))?;
+        let generic: Option<MethodTypeSignature> = self.generic_signature();
+        let element: TokenStream = generic_element(
+            generic
+                .as_ref()
+                .and_then(|g: &MethodTypeSignature| g.ret.as_ref()),
+            context,
+            mod_,
+        )?;
+
+        self.emit_converting_variant(
+            context,
+            cc,
+            mod_,
+            &method_name,
+            kind.unwrapped_type(&element),
+            |result: TokenStream| kind.unwrap(quote!(#result?), &element),
+            "returning a Rust `Option`. A `null` Optional is treated as empty",
+        )
+    }
+
+    /// The name of the `_async()` variant of this method, if it returns an awaitable `CompletionStage`.
+    pub fn async_variant_name(&self, context: &Context) -> Option<String> {
+        match &self.java.descriptor().return_type {
+            ReturnDescriptor::Return(desc)
+                if is_awaitable(desc, context) && !self.java.is_constructor() =>
+            {
+                let name: &str = self.rust_name()?;
+                Some(format!("{}_async", name.strip_prefix("r#").unwrap_or(name)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Emits a variant of this `CompletionStage`-returning method that returns a Rust `Future`, completed by a
+    /// `BiConsumer` proxy registered with `whenComplete`.
+    pub fn emit_async_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(method_name) = self.async_variant_name(context) else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let generic: Option<MethodTypeSignature> = self.generic_signature();
+        let element: TokenStream = generic_element(
+            generic
+                .as_ref()
+                .and_then(|g: &MethodTypeSignature| g.ret.as_ref()),
+            context,
+            mod_,
+        )?;
+        let consumer: TokenStream = context.java_to_rust_path(Id(COMPLETION_CONSUMER), mod_)?;
+
+        self.emit_converting_variant(
+            context,
+            cc,
+            mod_,
+            &method_name,
+            quote!(crate::futures::JavaFuture<#element>),
+            |result: TokenStream| {
+                quote!(
+                    let (__jni_future, __jni_completion) = crate::futures::JavaFuture::<#element>::new();
+                    match #result? {
+                        ::std::option::Option::Some(__jni_stage) => {
+                            let __jni_consumer = #consumer::new_proxy(__jni_stage.env(), ::std::sync::Arc::new(__jni_completion))?;
+                            crate::futures::when_complete(&__jni_stage, &__jni_consumer)?;
+                        }
+                        ::std::option::Option::None => __jni_completion.complete(None, None),
+                    }
+                    ::std::result::Result::Ok(__jni_future)
+                )
+            },
+            "returning a Rust `Future`, completed through `whenComplete`. A `null` stage completes with `None`",
+        )
+    }
+
+    /// Emits a variant of this method named `method_name`, which calls it and passes its result through `convert`
+    /// into a `Result<ret_decl, _>`.
+    ///
+    /// `docs` completes "Variant of [`Self::name`] ...".
+    #[allow(clippy::too_many_arguments)]
+    fn emit_converting_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+        method_name: &str,
+        ret_decl: TokenStream,
+        convert: impl FnOnce(TokenStream) -> TokenStream,
+        docs: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(target) = self.emitted_name() else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let MethodSignature {
+            params_decl,
+            arg_names,
+            ..
+        } = self.signature(context, cc, mod_)?;
+
+        let call_receiver: TokenStream = match self.java.is_static() {
+            true => quote!(__jni_env),
+            false => quote!(self),
+        };
+        let attributes: TokenStream = if self.java.deprecated() {
+            quote!(#[deprecated])
+        } else {
+            quote!()
+        };
+
+        let linked: String = match self.dispatched {
+            true => self.dispatch_name().unwrap_or_default(),
+            false => target.clone(),
+        };
+        let java_docs: String = match KnownDocsUrl::from_method(cc, self) {
+            Some(url) => format!("{url}"),
+            None => self.java.name().to_string(),
+        };
+        let docs: String = format!(
+            "{java_docs}\n\nVariant of [`Self::{}`] {docs}.",
+            linked.strip_prefix("r#").unwrap_or(&linked)
+        );
+
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let method_name: Ident = format_ident!("{method_name}");
+        let target: Ident = format_ident!("{target}");
+        let body: TokenStream = convert(quote!(Self::#target(#call_receiver, #(#arg_names),*)));
+
+        Ok(quote!(
+            #[doc = #docs]
+            #attributes
+            pub fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                #body
+            }
+        ))
+    }
+
+    /// The name of the varargs variant of this method, if it is one.
+    pub fn varargs_name(&self) -> Option<String> {
+        if !self.java.is_varargs() {
+            return None;
+        }
+        let name: &str = self.rust_name()?;
+        Some(format!(
+            "{}_varargs",
+            name.strip_prefix("r#").unwrap_or(name)
+        ))
+    }
+
+    /// The name of the `super_` variant of this method, if subclasses can override it.
+    pub fn super_variant_name(&self) -> Option<String> {
+        if self.java.is_static()
+            || self.java.is_constructor()
+            || self.java.is_static_init()
+            || self.java.is_final()
+            || self.java.is_private()
+            || self.java.is_abstract()
+            || self.class.is_final()
+        {
+            return None;
+        }
+        let name: &str = self.rust_name()?;
+        Some(format!("super_{}", name.strip_prefix("r#").unwrap_or(name)))
+    }
+
+    /// Emits a variant of this overridable method that calls this class' implementation through
+    /// `CallNonvirtual*MethodA`, bypassing overrides in subclasses and proxies.
+    pub fn emit_super_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(method_name) = self.super_variant_name() else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let MethodSignature {
+            params_decl,
+            params_array,
+            arg_conversions,
+            arg_names,
+            ret_decl,
+            ret_conversion,
+            ret_method_fragment,
+            ..
+        } = self.signature(context, cc, mod_)?;
+
+        let attributes: TokenStream = if self.java.deprecated() {
+            quote!(#[deprecated])
+        } else {
+            quote!()
+        };
+        let linked: String = match self.dispatched {
+            true => self.dispatch_name().unwrap_or_default(),
+            false => self.rust_name().unwrap_or_default().to_owned(),
+        };
+        let docs: String = match KnownDocsUrl::from_method(cc, self) {
+            Some(url) => format!("{url}"),
+            None => self.java.name().to_string(),
+        };
+        let docs: String = format!(
+            "{docs}\n\nNonvirtual variant of [`Self::{}`], calling this class' implementation even if `self` overrides it.",
+            linked.strip_prefix("r#").unwrap_or(&linked)
+        );
+
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let java_name: Literal = cstring(self.java.name());
+        let descriptor: Literal = cstring(&self.java.descriptor().to_string());
+        let method_name: Ident = format_ident!("{method_name}");
+
+        let jni_type: String = jni_function_type(ret_method_fragment);
+        let call: Ident = format_ident!("CallNonvirtual{jni_type}MethodA");
+        let trace_span: TokenStream = self.trace_span(context);
+        let result: TokenStream = match ret_method_fragment {
+            "object" => quote!(
+                (!__jni_result.is_null())
+                    .then(|| ::java_oxide::Local::from_raw(__jni_env, __jni_result))
+            ),
+            _ => quote!(__jni_result),
+        };
+
+        if context.config.src.macros {
+            return Ok(quote!(
+                #[doc = #docs]
+                #attributes
+                pub fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                    #trace_span
+                    let __jni_env = self.env();
+                    #arg_conversions
+                    crate::macros::call_nonvirtual_method!(
+                        #call(__jni_env, self), #java_name, #descriptor, [#(#arg_names),*], #throwable, |__jni_result| #result
+                    ) #ret_conversion
+                }
+            ));
+        }
+
+        Ok(quote!(
             #[doc = #docs]
             #attributes
             pub fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
                 static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                #trace_span
                 unsafe {
+                    let __jni_env = self.env();
+                    #arg_conversions
                     let __jni_args = [#params_array];
+                    let __jni_class = Self::__class_global_ref(__jni_env);
+                    let __jni_method = __METHOD.get_or_init(||
+                        ::java_oxide::JMethodID::from_raw(__jni_env.require_method(__jni_class, #java_name, #descriptor))
+                    ).as_raw();
+
+                    let __jni_raw_env = __jni_env.as_raw();
+                    let __jni_result = ((**__jni_raw_env).v1_1.#call)(__jni_raw_env, self.as_raw(), __jni_class, __jni_method, __jni_args.as_ptr());
+                    __jni_env.exception_check::<#throwable>().map(|()| #result) #ret_conversion
+                }
+            }
+        ))
+    }
+
+    /// The name of the `_unchecked()` variant of this method, if `cc` enables them.
+    pub fn unchecked_variant_name(&self, cc: &ClassConfig) -> Option<String> {
+        if !cc.unchecked_variants || self.java.is_constructor() || self.java.is_static_init() {
+            return None;
+        }
+        let name: &str = self.rust_name()?;
+        Some(format!(
+            "{}_unchecked",
+            name.strip_prefix("r#").unwrap_or(name)
+        ))
+    }
+
+    /// Emits an unsafe variant of this method that calls it without checking for a pending exception afterwards.
+    ///
+    /// Parameters and return values are bound as-is, skipping the fallible string, boxing and Optional conversions.
+    pub fn emit_unchecked_variant(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(method_name) = self.unchecked_variant_name(cc) else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let raw_cc: ClassConfig = ClassConfig {
+            rust_strings: false,
+            boxed_primitives: false,
+            optionals: false,
+            ..cc.clone()
+        };
+        let MethodSignature {
+            params_decl,
+            params_array,
+            ret_decl,
+            ret_method_fragment,
+            ..
+        } = self.signature(context, &raw_cc, mod_)?;
+
+        let attributes: TokenStream = if self.java.deprecated() {
+            quote!(#[deprecated])
+        } else {
+            quote!()
+        };
+        let linked: String = match self.dispatched {
+            true => self.dispatch_name().unwrap_or_default(),
+            false => self.rust_name().unwrap_or_default().to_owned(),
+        };
+        let docs: String = match KnownDocsUrl::from_method(cc, self) {
+            Some(url) => format!("{url}"),
+            None => self.java.name().to_string(),
+        };
+        let docs: String = format!(
+            "{docs}\n\nUnchecked variant of [`Self::{}`], returning the raw value without checking for a pending Java exception.\n\n# Safety\n\nThe method must not throw. A thrown exception is left pending, and most JNI calls made while it is pending are undefined behavior.",
+            linked.strip_prefix("r#").unwrap_or(&linked)
+        );
+
+        let (env_let, require_method, this_or_class): (TokenStream, Ident, TokenStream) =
+            match self.java.is_static() {
+                false => (
+                    quote!(let __jni_env = self.env();),
+                    format_ident!("require_method"),
+                    quote!(self.as_raw()),
+                ),
+                true => (
+                    quote!(),
+                    format_ident!("require_static_method"),
+                    quote!(__jni_class),
+                ),
+            };
+        let java_name: Literal = cstring(self.java.name());
+        let descriptor: Literal = cstring(&self.java.descriptor().to_string());
+        let method_name: Ident = format_ident!("{method_name}");
+
+        let jni_type: String = jni_function_type(ret_method_fragment);
+        let call: Ident = match self.java.is_static() {
+            false => format_ident!("Call{jni_type}MethodA"),
+            true => format_ident!("CallStatic{jni_type}MethodA"),
+        };
+        let trace_span: TokenStream = self.trace_span(context);
+        let result: TokenStream = match ret_method_fragment {
+            "object" => quote!(
+                (!__jni_result.is_null())
+                    .then(|| ::java_oxide::Local::from_raw(__jni_env, __jni_result))
+            ),
+            _ => quote!(__jni_result),
+        };
+
+        Ok(quote!(
+            #[doc = #docs]
+            #attributes
+            pub unsafe fn #method_name<'env>(#params_decl) -> #ret_decl {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                #trace_span
+                unsafe {
                     #env_let
+                    let __jni_args = [#params_array];
                     let __jni_class = Self::__class_global_ref(__jni_env);
                     let __jni_method = __METHOD.get_or_init(||
                         ::java_oxide::JMethodID::from_raw(__jni_env.#require_method(__jni_class, #java_name, #descriptor))
                     ).as_raw();
 
-                    #call
+                    let __jni_raw_env = __jni_env.as_raw();
+                    let __jni_result = ((**__jni_raw_env).v1_1.#call)(__jni_raw_env, #this_or_class, __jni_method, __jni_args.as_ptr());
+                    #result
                 }
             }
-        ));
+        ))
+    }
 
-        Ok(out)
+    /// Emits a variant of this varargs method taking the trailing array as a slice (primitives) or an
+    /// `IntoIterator` of arguments (objects), and allocating the Java array before the call.
+    pub fn emit_varargs(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+    ) -> anyhow::Result<TokenStream> {
+        let (Some(method_name), Some(target)) = (self.varargs_name(), self.emitted_name()) else {
+            return Err(anyhow::anyhow!("ERROR: Failed to mangle method name"));
+        };
+        let MethodSignature {
+            mut arg_names,
+            mut arg_types,
+            ret_decl,
+            ..
+        } = self.signature(context, cc, mod_)?;
+
+        let Some(last) = self.java.descriptor().parameters.last() else {
+            return Err(anyhow::anyhow!("ERROR: Varargs method without parameters"));
+        };
+        if last.dimensions == 0 {
+            return Err(anyhow::anyhow!("ERROR: Varargs parameter isn't an array"));
+        }
+        arg_names.pop();
+        arg_types.pop();
+
+        let element: FieldDescriptor = FieldDescriptor {
+            dimensions: last.dimensions - 1,
+            field_type: last.field_type.clone(),
+        };
+        let mut emit_reject_reasons: Vec<String> = Vec::new();
+        let element_type: TokenStream = emit_type(
+            &element,
+            context,
+            mod_,
+            RustTypeFlavor::Bare,
+            &mut emit_reject_reasons,
+        )?;
+        if !emit_reject_reasons.is_empty() {
+            return Err(anyhow::anyhow!(emit_reject_reasons.join("\n")));
+        }
+
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+
+        let (varargs_type, new_array): (TokenStream, TokenStream) = if element.dimensions == 0
+            && !matches!(element.field_type, FieldType::Object(_))
+        {
+            let array_type: TokenStream = emit_type(
+                last,
+                context,
+                mod_,
+                RustTypeFlavor::Bare,
+                &mut emit_reject_reasons,
+            )?;
+            (
+                quote!(&[#element_type]),
+                quote!(<#array_type as ::java_oxide::PrimitiveArray<#element_type>>::from(__jni_env, varargs)),
+            )
+        } else {
+            (
+                quote!(impl ::std::iter::IntoIterator<Item = impl ::java_oxide::AsArg<#element_type>, IntoIter: ::std::iter::ExactSizeIterator>),
+                quote!(::java_oxide::ObjectArray::<#element_type, #throwable>::new_from(__jni_env, varargs.into_iter())),
+            )
+        };
+
+        let (receiver, env_let, call_receiver): (TokenStream, TokenStream, TokenStream) =
+            match self.java.is_constructor() || self.java.is_static() {
+                true => (
+                    quote!(__jni_env: ::java_oxide::Env<'env>,),
+                    quote!(),
+                    quote!(__jni_env),
+                ),
+                false => (
+                    quote!(self: &::java_oxide::Ref<'env, Self>,),
+                    quote!(let __jni_env = self.env();),
+                    quote!(self),
+                ),
+            };
+
+        let attributes: TokenStream = if self.java.deprecated() {
+            quote!(#[deprecated])
+        } else {
+            quote!()
+        };
+
+        let linked: String = match self.dispatched {
+            true => self.dispatch_name().unwrap_or_default(),
+            false => target.clone(),
+        };
+        let docs: String = match KnownDocsUrl::from_method(cc, self) {
+            Some(url) => format!("{url}"),
+            None => self.java.name().to_string(),
+        };
+        let docs: String = format!(
+            "{docs}\n\nVarargs variant of [`Self::{}`], collecting `varargs` into the trailing Java array",
+            linked.strip_prefix("r#").unwrap_or(&linked)
+        );
+
+        let method_name: Ident = format_ident!("{method_name}");
+        let target: Ident = format_ident!("{target}");
+
+        Ok(quote!(
+            #[doc = #docs]
+            #attributes
+            pub fn #method_name<'env>(#receiver #(#arg_names: #arg_types,)* varargs: #varargs_type) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                #env_let
+                let __jni_varargs = #new_array;
+                Self::#target(#call_receiver, #(#arg_names,)* &__jni_varargs)
+            }
+        ))
+    }
+
+    /// Emits a method on a subtype that upcasts `self` and forwards to this method on `declaring`,
+    /// the superclass or interface that declares it.
+    ///
+    /// `visibility` is empty when emitting into a trait.
+    pub fn emit_inherited(
+        &self,
+        context: &Context,
+        cc: &ClassConfig,
+        mod_: &str,
+        declaring: &Class,
+        visibility: TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        let method_name: &str = self
+            .rust_name()
+            .ok_or_else(|| anyhow::anyhow!("ERROR: Failed to mangle method name"))?;
+        let MethodSignature {
+            params_decl,
+            arg_names,
+            ret_decl,
+            ..
+        } = self.signature(context, cc, mod_)?;
+
+        let attributes: TokenStream = if self.java.deprecated() {
+            quote!(#[deprecated])
+        } else {
+            quote!()
+        };
+
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let declaring_path: TokenStream = context.java_to_rust_path(declaring.java.path(), mod_)?;
+
+        let declaring_link: String = declaring_path.to_string().replace(' ', "");
+        let docs: String = match KnownDocsUrl::from_method(cc, self) {
+            Some(url) => format!("{url}"),
+            None => self.java.name().to_string(),
+        };
+        let docs: String = format!(
+            "{docs}\n\nInherited from [`{}::{method_name}`]({declaring_link}::{method_name})",
+            declaring.rust.struct_name
+        );
+        let method_name: Ident = format_ident!("{method_name}");
+
+        Ok(quote!(
+            #[doc = #docs]
+            #attributes
+            #visibility fn #method_name<'env>(#params_decl) -> ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>> {
+                let __jni_super: ::java_oxide::Ref<'env, #declaring_path> =
+                    unsafe { ::java_oxide::Ref::from_raw(self.env(), self.as_raw()) };
+                #declaring_path::#method_name(&__jni_super, #(#arg_names),*)
+            }
+        ))
     }
 }
@@ -1,23 +1,62 @@
 //! Rust generation logic
 
+mod boxed;
 mod class_proxy;
 mod classes;
+mod collections;
+mod contracts;
+mod features;
 mod fields;
+mod futures;
+mod inheritance;
 pub mod java_proxy;
 mod known_docs_url;
+mod lookups;
+mod manifest;
 mod methods;
 mod modules;
+mod optionals;
+mod overloads;
+mod report;
+mod validation;
 
-use self::{classes::Class, modules::Module};
-use crate::{config, io_data_err, parser_util};
+use self::{classes::Class, features::FeatureGraph, modules::Module};
+use crate::{
+    config, io_data_err,
+    manifest::{Manifest, ManifestClass},
+    parser_util,
+    prelude::*,
+    pretty_path,
+};
+use anyhow::Context as _;
+use cafebabe::descriptors::ClassName;
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{TokenStreamExt, format_ident, quote};
-use std::{collections::HashMap, ffi::CString, io, rc::Rc, str::FromStr};
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::{BTreeMap, HashMap},
+    ffi::CString,
+    io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+};
 
 pub struct Context<'a> {
     pub config: &'a config::Config,
     pub module: Module,
     pub all_classes: HashMap<String, Rc<Class>>,
+    /// Classes bound by `[[extern]]` crates, by JNI path
+    pub externs: HashMap<String, ExternClass>,
+    features: OnceCell<FeatureGraph>,
+    /// What [`Class::write`] emitted of every class written so far, by JNI path
+    records: RefCell<BTreeMap<String, ManifestClass>>,
+}
+
+/// A class bound by an `[[extern]]` crate.
+pub struct ExternClass {
+    pub crate_name: String,
+    pub class: ManifestClass,
 }
 
 impl<'a> Context<'a> {
@@ -26,6 +65,76 @@ impl<'a> Context<'a> {
             config,
             module: Default::default(),
             all_classes: HashMap::new(),
+            externs: HashMap::new(),
+            features: OnceCell::new(),
+            records: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// The Cargo features gating the bindings, once every class is added.
+    pub fn features(&self) -> &FeatureGraph {
+        self.features.get_or_init(|| FeatureGraph::new(self))
+    }
+
+    /// `#[cfg(...)]` gating the items of the Java class `path` outside of its bindings.
+    fn class_cfg(&self, path: &str) -> TokenStream {
+        features::cfg(&self.config.feature_for(path).into_iter().collect())
+    }
+
+    /// Whether the class `path` is bound, either here or by an `[[extern]]` crate.
+    pub fn is_bound(&self, path: &str) -> bool {
+        self.all_classes.contains_key(path) || self.externs.contains_key(path)
+    }
+
+    /// The `[[extern]]` crate binding `java.lang.Throwable`, whose support modules these bindings share instead of
+    /// emitting their own.
+    pub fn preamble_crate(&self) -> Option<&str> {
+        self.externs
+            .get("java/lang/Throwable")
+            .map(|extern_: &ExternClass| extern_.crate_name.as_str())
+    }
+
+    /// Whether the class `path` is bound here or by the [`Self::preamble_crate`], so it implements the traits of the
+    /// support modules these bindings use, such as `crate::boxed::Boxed`.
+    pub fn is_bound_with_preamble(&self, path: &str) -> bool {
+        self.all_classes.contains_key(path)
+            || self.externs.get(path).is_some_and(|extern_: &ExternClass| {
+                Some(extern_.crate_name.as_str()) == self.preamble_crate()
+            })
+    }
+
+    /// The generic `Signature` attribute of the bound class `path`, if any.
+    pub fn class_signature(&self, path: &str) -> Option<&str> {
+        match self.all_classes.get(path) {
+            Some(class) => class.java.signature(),
+            None => self.externs.get(path)?.class.signature.as_deref(),
+        }
+    }
+
+    /// The superclass and direct superinterfaces of the bound class `path`.
+    pub fn supertypes(&self, path: &str) -> Vec<String> {
+        if let Some(class) = self.all_classes.get(path) {
+            class
+                .java
+                .interfaces()
+                .map(|i: &ClassName<'_>| i.to_string())
+                .chain(
+                    class
+                        .java
+                        .super_path()
+                        .map(|s: parser_util::Id| s.as_str().to_owned()),
+                )
+                .collect()
+        } else if let Some(extern_) = self.externs.get(path) {
+            extern_
+                .class
+                .interfaces
+                .iter()
+                .chain(&extern_.class.superclass)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
         }
     }
 
@@ -34,11 +143,36 @@ impl<'a> Context<'a> {
             .unwrap()
     }
 
+    /// Enters a `tracing` span for a call to `member` of `class`, if enabled by `sources.tracing`.
+    ///
+    /// The span is compiled only under the output crate's `java-oxide-trace` feature.
+    pub fn trace_span(&self, class: &str, member: &str, descriptor: &str) -> TokenStream {
+        if !self.config.src.tracing {
+            return TokenStream::new();
+        }
+        quote!(
+            #[cfg(feature = "java-oxide-trace")]
+            let __jni_span = ::tracing::trace_span!("jni", class = #class, member = #member, descriptor = #descriptor).entered();
+        )
+    }
+
     pub fn java_to_rust_path(
         &self,
         java_class: parser_util::Id,
         curr_mod: &str,
     ) -> Result<TokenStream, anyhow::Error> {
+        if !self.all_classes.contains_key(java_class.as_str())
+            && let Some(extern_) = self.externs.get(java_class.as_str())
+        {
+            let crate_name: Ident = format_ident!("{}", extern_.crate_name);
+            let mut result: TokenStream = quote!(::#crate_name);
+            for ident in extern_.class.rust.split("::") {
+                let ident: Ident = format_ident!("{}", ident);
+                result.extend(quote!(::#ident));
+            }
+            return Ok(result);
+        }
+
         let jclass_mod: String = Class::mod_for(java_class)?;
         let jclass_name: String = Class::name_for(java_class)?;
         let mut result: TokenStream = TokenStream::new();
@@ -59,12 +193,36 @@ impl<'a> Context<'a> {
         Ok(result)
     }
 
+    /// Registers the classes bound by the `[[extern]]` crate `extern_`, so they're referenced instead of bound again.
+    pub fn add_extern(&mut self, extern_: &config::ExternConfig) -> anyhow::Result<()> {
+        let manifest: Manifest = Manifest::read(&extern_.manifest)
+            .with_context(|| format!("reading {}", pretty_path!(extern_.manifest)))?;
+        for class in manifest.classes {
+            self.externs.insert(
+                class.java.clone(),
+                ExternClass {
+                    crate_name: extern_.crate_name.clone(),
+                    class,
+                },
+            );
+        }
+        Ok(())
+    }
+
     pub fn add_class(&mut self, class: parser_util::JavaClass) -> Result<bool, anyhow::Error> {
         let class_config: config::ClassConfig<'_> =
             self.config.resolve_class(class.path().as_str());
         if !class_config.bind {
             return Ok(false);
         }
+        if let Some(extern_) = self.externs.get(class.path().as_str()) {
+            debug!(
+                "Skipping {:?}, already bound by the {:?} crate",
+                class.path().as_str(),
+                extern_.crate_name
+            );
+            return Ok(false);
+        }
 
         let java_path: String = class.path().as_str().to_string();
         let class: Rc<Class> = Rc::new(Class::new(class)?);
@@ -89,8 +247,201 @@ impl<'a> Context<'a> {
     }
 
     pub fn write(&self, out: &mut impl io::Write) -> anyhow::Result<()> {
-        write!(out, "{}\n\n", include_str!("preamble.rs"))?;
-        self.module.write(self, out)
+        if self.config.src.features {
+            info!(
+                "Declare these features in the [features] table of the bindings crate:\n{}",
+                self.features().manifest_table()
+            );
+        }
+        if self.config.src.tracing {
+            info!(
+                "Add these lines to the Cargo.toml of the bindings crate for tracing spans:\n\
+                 [dependencies]\ntracing = {{ version = \"0.1\", optional = true }}\n\n\
+                 [features]\njava-oxide-trace = [\"dep:tracing\"]"
+            );
+        }
+        let mut generated: Vec<u8> = Vec::new();
+        self.write_preamble(&mut generated)?;
+        self.write_crate_root(&mut generated)?;
+        self.module.write(self, None, &mut generated)?;
+        out.write_all(&modules::format_source(
+            generated,
+            self.config.src.max_width,
+        )?)?;
+        self.warn_unawaitable();
+        Ok(())
+    }
+
+    /// Generates a complete bindings crate in `dir`: its `Cargo.toml`, and a `src/lib.rs` declaring one module file
+    /// per Java package.
+    ///
+    /// Returns the path and contents of every file.
+    pub fn write_crate(&self, dir: &Path) -> anyhow::Result<Vec<(PathBuf, Vec<u8>)>> {
+        let src: PathBuf = dir.join("src");
+        let mut files: Vec<(PathBuf, Vec<u8>)> =
+            vec![(dir.join("Cargo.toml"), self.write_manifest(dir))];
+
+        let mut generated: Vec<u8> = Vec::new();
+        self.write_preamble(&mut generated)?;
+        self.write_crate_root(&mut generated)?;
+        self.module
+            .write_files(self, None, &src, &mut generated, &mut files)?;
+        files.push((
+            src.join("lib.rs"),
+            modules::format_source(generated, self.config.src.max_width)?,
+        ));
+
+        self.warn_unawaitable();
+        Ok(files)
+    }
+
+    /// Emits the hand-written support modules at the root of the bindings.
+    ///
+    /// They're re-exported from the [`Self::preamble_crate`], if any, so the classes it binds implement the same
+    /// traits, and the `java.lang` helpers are left to it. The macros are always emitted, as they can't be exported.
+    fn write_preamble(&self, out: &mut impl io::Write) -> anyhow::Result<()> {
+        writeln!(out, "{}", include_str!("preamble_attributes.rs"))?;
+        match self.preamble_crate() {
+            Some(crate_name) => writeln!(
+                out,
+                "pub use ::{crate_name}::{{boxed, cast, collections, contracts, futures, lookup, optionals, proxies, strings, validation}};\n"
+            )?,
+            None => {
+                writeln!(out, "{}", include_str!("preamble.rs"))?;
+                writeln!(out, "{}", include_str!("preamble_util.rs"))?;
+            }
+        }
+        writeln!(out, "{}", include_str!("preamble_macros.rs"))?;
+        Ok(())
+    }
+
+    /// Emits the `lang` module, bringing the `java.lang` classes the preamble needs into scope from wherever they're
+    /// bound.
+    fn write_lang(&self) -> anyhow::Result<String> {
+        let object: TokenStream =
+            self.java_to_rust_path(parser_util::Id("java/lang/Object"), "")?;
+        let string: TokenStream =
+            self.java_to_rust_path(parser_util::Id("java/lang/String"), "")?;
+        let throwable: TokenStream =
+            self.java_to_rust_path(parser_util::Id("java/lang/Throwable"), "")?;
+        Ok(modules::dumb_format(quote!(
+            mod lang {
+                pub use #object;
+                pub use #string;
+                pub use #throwable;
+            }
+        )))
+    }
+
+    /// Emits the generated items at the root of the bindings, following the preamble and preceding the Java packages.
+    fn write_crate_root(&self, out: &mut impl io::Write) -> anyhow::Result<()> {
+        if self.preamble_crate().is_none() {
+            writeln!(out, "{}", self.write_lang()?)?;
+        }
+        writeln!(out, "{}", self.write_prelude()?)?;
+        writeln!(out, "{}", self.write_validate_bindings())?;
+        Ok(())
+    }
+
+    /// Emits the `Cargo.toml` of a bindings crate generated in `dir`, named after the directory.
+    fn write_manifest(&self, dir: &Path) -> Vec<u8> {
+        let name: String = dir
+            .file_name()
+            .map(|name| {
+                name.to_string_lossy()
+                    .replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "-")
+            })
+            .unwrap_or_else(|| "bindings".to_owned());
+        let mut manifest: String = format!(
+            "[package]\nname = {name:?}\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\njava-oxide = {}\n",
+            self.config.src.runtime
+        );
+        if self.config.src.tracing {
+            manifest.push_str("tracing = { version = \"0.1\", optional = true }\n");
+        }
+        if self.config.src.tracing || self.config.src.features {
+            manifest.push_str("\n[features]\n");
+        }
+        if self.config.src.tracing {
+            manifest.push_str("java-oxide-trace = [\"dep:tracing\"]\n");
+        }
+        if self.config.src.features {
+            manifest.push_str(&self.features().manifest_table());
+        }
+        manifest.into_bytes()
+    }
+
+    /// Emits `validate_bindings()` and `validate_bindings_and_proxies()`, checking every public bound class.
+    fn write_validate_bindings(&self) -> String {
+        let mut classes: Vec<&Rc<Class>> = self.all_classes.values().collect();
+        classes.sort_by(|a, b| a.java.path().as_str().cmp(b.java.path().as_str()));
+
+        let mut checks: TokenStream = TokenStream::new();
+        for class in classes {
+            // Private classes can't be named from the crate root
+            let cc: config::ClassConfig<'_> = self.config.resolve_class(class.java.path().as_str());
+            if !(class.java.is_public() || cc.bind_private_classes) {
+                continue;
+            }
+            let mut path: TokenStream = quote!(crate::);
+            for ident in class.rust.mod_.split("::") {
+                let ident: Ident = format_ident!("{ident}");
+                path.extend(quote!(#ident::));
+            }
+            let struct_name: Ident = format_ident!("{}", class.rust.struct_name);
+            let cfg: TokenStream = self.class_cfg(class.java.path().as_str());
+            checks.extend(
+                quote!(#cfg #path #struct_name::__validate_bindings(env, &mut report, proxies);),
+            );
+        }
+
+        modules::dumb_format(quote!(
+            /// Resolves every class, method and field these bindings use against the running JVM, collecting the
+            /// missing ones instead of panicking on first use.
+            pub fn validate_bindings(env: ::java_oxide::Env) -> validation::Report {
+                validate(env, false)
+            }
+
+            /// Like [`validate_bindings`], but also checks every Java proxy class, registering its native methods.
+            pub fn validate_bindings_and_proxies(env: ::java_oxide::Env) -> validation::Report {
+                validate(env, true)
+            }
+
+            fn validate(env: ::java_oxide::Env, proxies: bool) -> validation::Report {
+                let mut report = validation::Report::default();
+                #checks
+                report
+            }
+        ))
+    }
+
+    /// Emits the `prelude` module, bringing the casting helpers and every interface extension trait into scope.
+    fn write_prelude(&self) -> anyhow::Result<String> {
+        let mut classes: Vec<&Rc<Class>> = self.all_classes.values().collect();
+        classes.sort_by(|a, b| a.java.path().as_str().cmp(b.java.path().as_str()));
+
+        let mut uses: TokenStream = TokenStream::new();
+        for class in classes {
+            let Some(trait_name) = class.interface_trait_name(self) else {
+                continue;
+            };
+            let trait_name: Ident = format_ident!("{trait_name}");
+            let mut path: TokenStream = quote!(crate::);
+            for ident in class.rust.mod_.split("::") {
+                let ident: Ident = format_ident!("{ident}");
+                path.extend(quote!(#ident::));
+            }
+            let cfg: TokenStream = self.class_cfg(class.java.path().as_str());
+            uses.extend(quote!(#cfg pub use #path #trait_name as _;));
+        }
+
+        Ok(modules::dumb_format(quote!(
+            /// Traits needed to call interface methods on implementors, and to cast between bound classes.
+            pub mod prelude {
+                pub use crate::cast::InstanceOf;
+                #uses
+            }
+        )))
     }
 }
 
@@ -1,8 +1,16 @@
-use super::classes::Class;
+use super::{classes::Class, features::cfg_any};
 use crate::emit::Context;
+use anyhow::Context as _;
 use core::fmt;
 use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
-use std::{collections::BTreeMap, fmt::Write, io, rc::Rc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    rc::Rc,
+};
 
 #[derive(Debug, Default)]
 pub struct Module {
@@ -12,17 +20,61 @@ pub struct Module {
 }
 
 impl Module {
-    pub fn write(&self, context: &Context, out: &mut impl io::Write) -> anyhow::Result<()> {
+    /// Writes the submodules and classes of this module, which is gated behind any of the features in `gate`.
+    pub fn write(
+        &self,
+        context: &Context,
+        gate: Option<&BTreeSet<String>>,
+        out: &mut impl io::Write,
+    ) -> anyhow::Result<()> {
         for (name, module) in self.modules.iter() {
             writeln!(out)?;
 
+            let module_gate: Option<BTreeSet<String>> = module.features(context);
+            writeln!(out, "{}", module_cfg(gate, module_gate.as_ref()))?;
             writeln!(out, "pub mod {name} {{")?;
-            module.write(context, out)?;
+            module.write(context, module_gate.as_ref(), out)?;
             writeln!(out, "}}")?;
         }
 
+        self.write_classes(context, gate, out)
+    }
+
+    /// Like [`Self::write`], but declares submodules with `pub mod name;` and adds their `mod.rs` to `files`, under
+    /// `dir`.
+    pub fn write_files(
+        &self,
+        context: &Context,
+        gate: Option<&BTreeSet<String>>,
+        dir: &Path,
+        out: &mut impl io::Write,
+        files: &mut Vec<(PathBuf, Vec<u8>)>,
+    ) -> anyhow::Result<()> {
+        for (name, module) in self.modules.iter() {
+            let module_gate: Option<BTreeSet<String>> = module.features(context);
+            writeln!(out, "{}", module_cfg(gate, module_gate.as_ref()))?;
+            writeln!(out, "pub mod {name};")?;
+
+            let dir: PathBuf = dir.join(name);
+            let mut module_out: Vec<u8> = Vec::new();
+            module.write_files(context, module_gate.as_ref(), &dir, &mut module_out, files)?;
+            files.push((
+                dir.join("mod.rs"),
+                format_source(module_out, context.config.src.max_width)?,
+            ));
+        }
+
+        self.write_classes(context, gate, out)
+    }
+
+    fn write_classes(
+        &self,
+        context: &Context,
+        gate: Option<&BTreeSet<String>>,
+        out: &mut impl io::Write,
+    ) -> anyhow::Result<()> {
         for (_, class) in self.classes.iter() {
-            let res: TokenStream = class.write(context)?;
+            let res: TokenStream = class.write(context, gate)?;
             out.write_all(dumb_format(res).as_bytes())?;
         }
 
@@ -30,13 +82,60 @@ impl Module {
     }
 }
 
+/// The `#[cfg(...)]` of a submodule gated behind any of the features in `gate`, inside a module gated behind `parent`.
+fn module_cfg(parent: Option<&BTreeSet<String>>, gate: Option<&BTreeSet<String>>) -> String {
+    match gate {
+        Some(gate) if parent != Some(gate) => cfg_any(gate).to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Formats the generated Rust `source` with `prettyplease`, then with `rustfmt` if it has to fit in `max_width` columns
+/// rather than the fixed width of `prettyplease`.
+///
+/// Fails if `source` doesn't parse, as the generated code is then broken.
+pub fn format_source(source: Vec<u8>, max_width: Option<usize>) -> anyhow::Result<Vec<u8>> {
+    let source: String = String::from_utf8(source)?;
+    let file: syn::File = syn::parse_file(&source).context("parsing the generated code")?;
+    let formatted: String = prettyplease::unparse(&file);
+    match max_width {
+        Some(max_width) => rustfmt(formatted, max_width),
+        None => Ok(formatted.into_bytes()),
+    }
+}
+
+/// Reformats `source` with `rustfmt` to fit in `max_width` columns.
+fn rustfmt(source: String, max_width: usize) -> anyhow::Result<Vec<u8>> {
+    let mut child: std::process::Child = Command::new("rustfmt")
+        .args(["--edition", "2024", "--config"])
+        .arg(format!("max_width={max_width}"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("running rustfmt for 'source.max-width'")?;
+    // Written from another thread, as rustfmt may fill its output pipe before reading all of its input
+    let mut stdin: std::process::ChildStdin = child.stdin.take().unwrap();
+    let writer: std::thread::JoinHandle<io::Result<()>> =
+        std::thread::spawn(move || stdin.write_all(source.as_bytes()));
+    let output: std::process::Output = child.wait_with_output()?;
+    writer.join().unwrap()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "rustfmt failed to format the generated code:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
 /// Convert tokenstream to string, doing a best-effort formatting
 /// inserting newlines at `;` and `{}`.
 ///
-/// The user is supposed to run the output through `rustfmt`, this is
-/// intended just to prevent the output from being a single huge line
-/// to make debugging syntax errors easier.
-fn dumb_format(ts: TokenStream) -> String {
+/// The output is then run through [`format_source`], this is
+/// intended just to keep it readable when that fails, to make
+/// debugging syntax errors easier.
+pub fn dumb_format(ts: TokenStream) -> String {
     let mut f: DumbFormatter = DumbFormatter {
         space: false,
         after_newline: true,
@@ -132,3 +231,21 @@ impl DumbFormatter {
         }
     }
 }
+
+#[test]
+fn format_source_test() {
+    let source = || {
+        b"pub fn f(first_argument: u32, second_argument: u32) -> u32 { first_argument }".to_vec()
+    };
+    assert_eq!(
+        String::from_utf8(format_source(source(), None).unwrap()).unwrap(),
+        "pub fn f(first_argument: u32, second_argument: u32) -> u32 {\n    first_argument\n}\n"
+    );
+    let narrow: String = String::from_utf8(format_source(source(), Some(40)).unwrap()).unwrap();
+    assert!(
+        narrow.lines().all(|line: &str| line.len() <= 40),
+        "{narrow}"
+    );
+
+    assert!(format_source(b"pub fn f( {".to_vec(), None).is_err());
+}
@@ -0,0 +1,156 @@
+use super::classes::Class;
+use crate::{
+    emit::Context,
+    parser_util::{
+        Id,
+        signature::{TypeArgument, TypeSignature},
+    },
+};
+use cafebabe::descriptors::{FieldDescriptor, FieldType};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+/// The value held by a `java.util.Optional*` class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionalKind {
+    /// `java.util.Optional<T>`
+    Object,
+    Int,
+    Long,
+    Double,
+}
+
+impl OptionalKind {
+    fn from_class(class: &str) -> Option<Self> {
+        Some(match class {
+            "java/util/Optional" => Self::Object,
+            "java/util/OptionalInt" => Self::Int,
+            "java/util/OptionalLong" => Self::Long,
+            "java/util/OptionalDouble" => Self::Double,
+            _ => return None,
+        })
+    }
+
+    /// The Rust primitive held by `OptionalInt` and friends.
+    fn primitive(self) -> Option<TokenStream> {
+        match self {
+            Self::Object => None,
+            Self::Int => Some(quote!(i32)),
+            Self::Long => Some(quote!(i64)),
+            Self::Double => Some(quote!(f64)),
+        }
+    }
+
+    /// Names of the `crate::optionals` functions unwrapping and building this kind of Optional.
+    fn helpers(self) -> (Ident, Ident) {
+        match self {
+            Self::Object => (format_ident!("get"), format_ident!("of")),
+            Self::Int => (format_ident!("get_int"), format_ident!("of_int")),
+            Self::Long => (format_ident!("get_long"), format_ident!("of_long")),
+            Self::Double => (format_ident!("get_double"), format_ident!("of_double")),
+        }
+    }
+
+    /// The `crate::optionals::IntoOptional` bound accepted for parameters of this kind, holding `element`.
+    pub fn into_optional(self, optional: &TokenStream, element: &TokenStream) -> TokenStream {
+        match self {
+            Self::Object => quote!(crate::optionals::IntoOptional<#optional, #element>),
+            _ => quote!(crate::optionals::IntoOptional<#optional>),
+        }
+    }
+
+    /// The `Option` a returned Optional of this kind unwraps into, holding `element`.
+    pub fn unwrapped_type(self, element: &TokenStream) -> TokenStream {
+        match self.primitive() {
+            Some(primitive) => quote!(::std::option::Option<#primitive>),
+            None => quote!(::std::option::Option<::java_oxide::Local<'env, #element>>),
+        }
+    }
+
+    /// Unwraps `value`, an `Option<Local<'env, Optional>>`, into [`Self::unwrapped_type`].
+    pub fn unwrap(self, value: TokenStream, element: &TokenStream) -> TokenStream {
+        let (get, _) = self.helpers();
+        match self {
+            Self::Object => quote!(crate::optionals::#get::<#element>(#value)),
+            _ => quote!(crate::optionals::#get(#value)),
+        }
+    }
+}
+
+/// If `descriptor` is a `java.util.Optional*` class implementing `crate::optionals::IntoOptional`, returns which one.
+pub fn optional_kind(descriptor: &FieldDescriptor, context: &Context) -> Option<OptionalKind> {
+    let FieldType::Object(class_name) = &descriptor.field_type else {
+        return None;
+    };
+    let class: Id<'_> = Id::from(class_name);
+    if descriptor.dimensions != 0 || !context.is_bound_with_preamble(class.as_str()) {
+        return None;
+    }
+    OptionalKind::from_class(class.as_str())
+}
+
+impl Class {
+    /// Emits the `crate::optionals::IntoOptional` impl building a `java.util.Optional*` class from a Rust `Option`.
+    ///
+    /// Emits nothing for other classes.
+    pub fn write_optional_impl(
+        &self,
+        context: &Context,
+        gate: &TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        let Some(kind) = OptionalKind::from_class(self.java.path().as_str()) else {
+            return Ok(TokenStream::new());
+        };
+
+        let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+        let throwable: TokenStream = context.throwable_rust_path(&self.rust.mod_);
+        let (_, of) = kind.helpers();
+
+        let (generics, trait_, value, build) = match kind.primitive() {
+            Some(primitive) => (
+                quote!(),
+                quote!(crate::optionals::IntoOptional<#rust_name>),
+                primitive,
+                quote!(crate::optionals::#of::<#rust_name>(env, self)),
+            ),
+            None => (
+                quote!(<T: ::java_oxide::ReferenceType, A: ::java_oxide::AsArg<T>>),
+                quote!(crate::optionals::IntoOptional<#rust_name, T>),
+                quote!(A),
+                quote!(crate::optionals::#of::<#rust_name, T>(env, self)),
+            ),
+        };
+
+        Ok(quote!(
+            #gate
+            impl #generics #trait_ for ::std::option::Option<#value> {
+                fn into_optional<'env>(self, env: ::java_oxide::Env<'env>) -> ::std::result::Result<crate::optionals::OptionalArg<'env, #rust_name>, ::java_oxide::Local<'env, #throwable>> {
+                    ::std::result::Result::Ok(crate::optionals::OptionalArg::Local(#build?))
+                }
+            }
+        ))
+    }
+}
+
+/// The Rust type of the first type argument of a class typed as `signature` in a generic `Signature`, such as the
+/// element of an `Optional<T>` or `CompletableFuture<T>`.
+///
+/// Falls back to `Object` when the type argument is erased, a type variable, or not bound.
+pub fn generic_element(
+    signature: Option<&TypeSignature>,
+    context: &Context,
+    mod_: &str,
+) -> anyhow::Result<TokenStream> {
+    let element: Option<&str> = match signature {
+        Some(TypeSignature::Class(class)) => match class.type_args.first() {
+            Some(TypeArgument::Exact(TypeSignature::Class(element)))
+            | Some(TypeArgument::Extends(TypeSignature::Class(element))) => Some(&element.path),
+            _ => None,
+        },
+        _ => None,
+    };
+    let element: &str = element
+        .filter(|path: &&str| context.is_bound(path))
+        .unwrap_or("java/lang/Object");
+    context.java_to_rust_path(Id(element), mod_)
+}
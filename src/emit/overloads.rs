@@ -0,0 +1,324 @@
+use super::{
+    classes::Class,
+    fields::{RustTypeFlavor, emit_type},
+    methods::{Method, MethodSignature},
+};
+use crate::{config::ClassConfig, emit::Context, prelude::*};
+use cafebabe::descriptors::FieldType;
+use proc_macro2::{Ident, Literal, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+
+impl Class {
+    /// The name of the trait implemented by every argument tuple accepted by the `name` dispatching method.
+    pub fn overload_trait_name(&self, name: &str) -> String {
+        overload_trait_name(&self.rust.struct_name, name)
+    }
+
+    /// Path to the `name` overload trait, as seen from `mod_`.
+    pub fn overload_trait_path(&self, name: &str, mod_: &str) -> TokenStream {
+        let trait_name: Ident = format_ident!("{}", self.overload_trait_name(name));
+        if self.rust.mod_ == mod_ {
+            return quote!(#trait_name);
+        }
+        let mut path: TokenStream = quote!(crate::);
+        for ident in self.rust.mod_.split("::") {
+            let ident: Ident = format_ident!("{ident}");
+            path.extend(quote!(#ident::));
+        }
+        quote!(#path #trait_name)
+    }
+
+    /// Emits one dispatching method per group of dispatched overloads, along with the trait selecting the overload
+    /// from the argument tuple.
+    ///
+    /// Returns the methods to place in the `impl` block of the class, and the items to place next to it.
+    pub fn write_overload_dispatch(
+        &self,
+        context: &Context,
+        methods: &[Method],
+        gate: &TokenStream,
+    ) -> anyhow::Result<(TokenStream, TokenStream)> {
+        let mut contents: TokenStream = TokenStream::new();
+        let mut out: TokenStream = TokenStream::new();
+
+        let mut seen: HashSet<String> = HashSet::new();
+        for method in methods.iter().filter(|m: &&Method<'_>| m.is_dispatched()) {
+            let Some(name) = method.dispatch_name() else {
+                continue;
+            };
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let overloads: Vec<&Method<'_>> = methods
+                .iter()
+                .filter(|m: &&Method<'_>| {
+                    m.is_dispatched() && m.dispatch_name().as_deref() == Some(name.as_str())
+                })
+                .collect();
+
+            let mut impls: TokenStream = TokenStream::new();
+            let mut overload_params: Vec<String> = Vec::new();
+            let mut markers: Vec<String> = Vec::new();
+            for overload in &overloads {
+                match self.emit_overload_impl(context, &name, overload) {
+                    Ok((res, marker)) => {
+                        let cfg: TokenStream =
+                            context.member_cfg(self.java.path(), &overload.referenced_classes());
+                        impls.extend(quote!(#gate #cfg #res));
+                        overload_params.push(java_parameters(overload));
+                        markers.push(marker.to_string().replace(' ', ""));
+                    }
+                    Err(e) => trace!(
+                        "OVERLOAD REJECTED - \"{}.{}{}\":\n{}",
+                        self.java.path().as_str(),
+                        overload.java.name(),
+                        overload.java.descriptor(),
+                        e
+                    ),
+                }
+            }
+            if overload_params.is_empty() {
+                continue;
+            }
+
+            let method_name: Ident = format_ident!("{name}");
+            let trait_name: Ident = format_ident!("{}", self.overload_trait_name(&name));
+            let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+
+            let java_name: &str = match method.java.is_constructor() {
+                true => &self.rust.struct_name,
+                false => method.java.name(),
+            };
+            let docs: String = format!(
+                "{java_name}\n\nOverloaded, the argument tuple selects one of:\n{}\n\n\
+                 Arguments are converted like those of the other methods, so they may fit several overloads (a \
+                 `String` is also an `Object`). Name the Java parameter types of the overload to call then, such as \
+                 `{name}::<{}, _>`.",
+                overload_params
+                    .iter()
+                    .map(|params: &String| format!("- `{java_name}({params})`"))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                markers[0]
+            );
+            let trait_docs: String = format!(
+                "Argument tuples accepted by [`{}::{name}`]",
+                self.rust.struct_name
+            );
+
+            let (receiver, receiver_arg, call): (TokenStream, TokenStream, TokenStream) =
+                match method.java.is_static() || method.java.is_constructor() {
+                    true => (
+                        quote!(__jni_env: ::java_oxide::Env<'env>),
+                        quote!(__jni_env: ::java_oxide::Env<'env>),
+                        quote!(args.__call(__jni_env)),
+                    ),
+                    false => (
+                        quote!(self: &::java_oxide::Ref<'env, Self>),
+                        quote!(__jni_this: &::java_oxide::Ref<'env, #rust_name>),
+                        quote!(args.__call(self)),
+                    ),
+                };
+
+            contents.extend(quote!(
+                #[doc = #docs]
+                pub fn #method_name<'env, __M, __A: #trait_name<'env, __M>>(#receiver, args: __A) -> __A::Output {
+                    #call
+                }
+            ));
+            out.extend(quote!(
+                #[doc = #trait_docs]
+                #gate
+                pub trait #trait_name<'env, __M> {
+                    type Output;
+
+                    #[doc(hidden)]
+                    fn __call(self, #receiver_arg) -> Self::Output;
+                }
+
+                #impls
+            ));
+        }
+
+        Ok((contents, out))
+    }
+
+    /// Implements the overload trait for the argument tuple of `overload`, forwarding to its hidden method.
+    ///
+    /// Returns the impl, and the marker type selecting it.
+    fn emit_overload_impl(
+        &self,
+        context: &Context,
+        name: &str,
+        overload: &Method,
+    ) -> anyhow::Result<(TokenStream, TokenStream)> {
+        let mod_: &str = &self.rust.mod_;
+        let cc: ClassConfig<'_> = context.config.resolve_class(self.java.path().as_str());
+        let mut reject_reasons: Vec<String> = Vec::new();
+
+        let MethodSignature {
+            arg_types,
+            ret_decl,
+            ..
+        } = overload.signature(context, &cc, mod_)?;
+        let ret_decl: TokenStream = match overload.java.is_constructor() {
+            true => {
+                let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+                quote!(::java_oxide::Local<'env, #rust_name>)
+            }
+            false => ret_decl,
+        };
+
+        // `impl Trait` arguments become type parameters of the impl, so the tuple accepts the same arguments as the
+        // hidden method. The marker (the erased Java parameter types) keeps impls of overloads with the same arity
+        // from overlapping, inference then picks the only one whose bounds the arguments satisfy.
+        let mut generics: Vec<TokenStream> = Vec::new();
+        let mut tuple_types: Vec<TokenStream> = Vec::new();
+        for (idx, arg_type) in arg_types.into_iter().enumerate() {
+            match impl_bound(&arg_type) {
+                Some(bound) => {
+                    let param: Ident = format_ident!("__A{idx}");
+                    generics.push(quote!(#param: #bound));
+                    tuple_types.push(quote!(#param));
+                }
+                None => tuple_types.push(arg_type),
+            }
+        }
+        let mut marker: Vec<TokenStream> = Vec::new();
+        for arg in &overload.java.descriptor().parameters {
+            marker.push(emit_type(
+                arg,
+                context,
+                mod_,
+                RustTypeFlavor::Bare,
+                &mut reject_reasons,
+            )?);
+        }
+        if !reject_reasons.is_empty() {
+            return Err(anyhow::anyhow!(reject_reasons.join("\n")));
+        }
+
+        let rust_name: Ident = format_ident!("{}", self.rust.struct_name);
+        let trait_name: Ident = format_ident!("{}", self.overload_trait_name(name));
+        let target: Ident = format_ident!("{}", overload.emitted_name().unwrap_or_default());
+        let throwable: TokenStream = context.throwable_rust_path(mod_);
+        let fields: Vec<Literal> = (0..tuple_types.len())
+            .map(Literal::usize_unsuffixed)
+            .collect();
+
+        let (receiver_arg, receiver): (TokenStream, TokenStream) =
+            match overload.java.is_static() || overload.java.is_constructor() {
+                true => (
+                    quote!(__jni_env: ::java_oxide::Env<'env>),
+                    quote!(__jni_env),
+                ),
+                false => (
+                    quote!(__jni_this: &::java_oxide::Ref<'env, #rust_name>),
+                    quote!(__jni_this),
+                ),
+            };
+
+        let marker: TokenStream = quote!((#(#marker,)*));
+        let res: TokenStream = quote!(
+            impl<'env, #(#generics),*> #trait_name<'env, #marker> for (#(#tuple_types,)*) {
+                type Output = ::std::result::Result<#ret_decl, ::java_oxide::Local<'env, #throwable>>;
+
+                fn __call(self, #receiver_arg) -> Self::Output {
+                    #rust_name::#target(#receiver, #(self.#fields),*)
+                }
+            }
+        );
+        Ok((res, marker))
+    }
+}
+
+/// The name of the overload trait of the `name` dispatching method of the Rust struct `struct_name`, see
+/// [`Class::overload_trait_name`].
+pub fn overload_trait_name(struct_name: &str, name: &str) -> String {
+    format!(
+        "{struct_name}_{}_Args",
+        name.strip_prefix("r#").unwrap_or(name)
+    )
+}
+
+/// Emits a method on a subtype that upcasts `self` and forwards to the `name` dispatching method of `declaring`.
+///
+/// `visibility` is empty when emitting into a trait.
+pub fn emit_inherited_dispatch(
+    context: &Context,
+    mod_: &str,
+    declaring: &Class,
+    name: &str,
+    visibility: TokenStream,
+) -> anyhow::Result<TokenStream> {
+    let declaring_path: TokenStream = context.java_to_rust_path(declaring.java.path(), mod_)?;
+    let trait_path: TokenStream = declaring.overload_trait_path(name, mod_);
+    Ok(forward_dispatch(
+        &declaring_path,
+        &trait_path,
+        &declaring.rust.struct_name,
+        name,
+        visibility,
+    ))
+}
+
+/// Emits a method on a subtype that upcasts `self` to `declaring_path`, the Rust struct `struct_name`, and forwards to
+/// its `name` dispatching method, whose overload trait is `trait_path`.
+pub fn forward_dispatch(
+    declaring_path: &TokenStream,
+    trait_path: &TokenStream,
+    struct_name: &str,
+    name: &str,
+    visibility: TokenStream,
+) -> TokenStream {
+    let method_name: Ident = format_ident!("{name}");
+    let docs: String = format!(
+        "Inherited from [`{struct_name}::{name}`]({declaring_path}::{name})",
+        declaring_path = declaring_path.to_string().replace(' ', "")
+    );
+
+    quote!(
+        #[doc = #docs]
+        #visibility fn #method_name<'env, __M, __A: #trait_path<'env, __M>>(self: &::java_oxide::Ref<'env, Self>, args: __A) -> __A::Output {
+            let __jni_super: ::java_oxide::Ref<'env, #declaring_path> =
+                unsafe { ::java_oxide::Ref::from_raw(self.env(), self.as_raw()) };
+            #declaring_path::#method_name(&__jni_super, args)
+        }
+    )
+}
+
+/// The bound of an `impl Trait` argument type.
+fn impl_bound(arg_type: &TokenStream) -> Option<TokenStream> {
+    let mut tokens = arg_type.clone().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident == "impl" => Some(tokens.collect()),
+        _ => None,
+    }
+}
+
+/// Java parameter list of `method`, e.g. `int, String[]`.
+fn java_parameters(method: &Method) -> String {
+    let mut params: Vec<String> = Vec::new();
+    for arg in &method.java.descriptor().parameters {
+        let mut param: String = match &arg.field_type {
+            FieldType::Boolean => "boolean".to_owned(),
+            FieldType::Byte => "byte".to_owned(),
+            FieldType::Char => "char".to_owned(),
+            FieldType::Short => "short".to_owned(),
+            FieldType::Integer => "int".to_owned(),
+            FieldType::Long => "long".to_owned(),
+            FieldType::Float => "float".to_owned(),
+            FieldType::Double => "double".to_owned(),
+            FieldType::Object(class_name) => {
+                let class: &str = class_name.as_ref();
+                class.rsplit(['/', '$']).next().unwrap_or(class).to_owned()
+            }
+        };
+        for _ in 0..arg.dimensions {
+            param.push_str("[]");
+        }
+        params.push(param);
+    }
+    params.join(", ")
+}
@@ -1,120 +1,1048 @@
-#![feature(arbitrary_self_types)] // Enable arbitrary self types for smart pointers
-// #![allow(unused_imports)]
-#![allow(non_camel_case_types)] // We map Java inner classes to Outer_Inner
-#![allow(dead_code)] // We generate structs for private Java types too, just in case.
-#![allow(deprecated)] // We're generating deprecated types/methods
-#![allow(non_upper_case_globals)] // We might be generating Java style fields/methods
-#![allow(non_snake_case)] // We might be generating Java style fields/methods
-#![allow(clippy::all)] // we don't ensure generated bindings are clippy-compliant at all.
-#![allow(unsafe_code)] // play nice if user has `deny(unsafe_code)` in their crate.
-
-mod util {
-    use super::java::lang::{String as JString, Throwable};
-    use java_oxide::{sys::jsize, Env, JavaDebug, Local, Ref, StringChars, ThrowableType};
-    use std::{char::DecodeUtf16Error, fmt};
-
-    impl JavaDebug for Throwable {
-        fn fmt(self: &Ref<'_, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            writeln!(f, "java::lang::Throwable")?;
-
-            match self.getMessage() {
-                Err(_) => writeln!(f, "    getMessage:            N/A (threw an exception!)")?,
-                Ok(None) => writeln!(f, "    getMessage:            N/A (returned null)")?,
-                Ok(Some(message)) => writeln!(f, "    getMessage:            {:?}", message)?,
-            }
-
-            match self.getLocalizedMessage() {
-                Err(_) => writeln!(f, "    getLocalizedMessage:   N/A (threw an exception!)")?,
-                Ok(None) => writeln!(f, "    getLocalizedMessage:   N/A (returned null)")?,
-                Ok(Some(message)) => writeln!(f, "    getLocalizedMessage:   {:?}", message)?,
-            }
-
-            match self.getStackTrace() {
-                Err(_) => writeln!(f, "    getStackTrace:         N/A (threw an exception!)")?,
-                Ok(None) => writeln!(f, "    getStackTrace:         N/A (returned null)")?,
-                Ok(Some(stack_trace)) => {
-                    writeln!(f, "    getStackTrace:")?;
-                    for frame in stack_trace.iter() {
-                        match frame {
-                            None => writeln!(f, "        N/A (frame was null)")?,
-                            Some(frame) => {
-                                let file_line = match (frame.getFileName(), frame.getLineNumber()) {
-                                    (Ok(Some(file)), Ok(line)) => {
-                                        format!("{}({}):", file.to_string_lossy(), line)
-                                    }
-                                    (Ok(Some(file)), _) => format!("{}:", file.to_string_lossy()),
-                                    (_, _) => {
-                                        "N/A (getFileName threw an exception or returned null)"
-                                            .to_owned()
-                                    }
-                                };
-
-                                let class_method = match (frame.getClassName(), frame.getMethodName()) {
-                                    (Ok(Some(class)), Ok(Some(method))) => {
-                                        format!("{}.{}", class.to_string_lossy(), method.to_string_lossy())
-                                    }
-                                    (Ok(Some(class)), _) => class.to_string_lossy(),
-                                    (_, Ok(Some(method))) => method.to_string_lossy(),
-                                    (_, _) => "N/A (getClassName + getMethodName threw exceptions or returned null)"
-                                        .to_owned(),
-                                };
-
-                                writeln!(f, "        {:120}{}", file_line, class_method)?;
-                            }
-                        }
+/// Checked downcasting for references to bound classes.
+pub mod cast {
+    use java_oxide::{sys::jobject, Env, Local, Ref, ReferenceType};
+
+    /// A bound Java class, whose `java.lang.Class` is looked up once and cached by the generated bindings.
+    pub unsafe trait BoundClass: ReferenceType {
+        /// Returns the cached global reference to this class' `java.lang.Class`.
+        fn class_global_ref(env: Env<'_>) -> jobject;
+    }
+
+    /// `instanceof` checks and checked casts, backed by JNI's `IsInstanceOf`.
+    pub trait InstanceOf<'env>: Sized {
+        /// The same kind of reference, pointing at a `T`.
+        type Cast<T: BoundClass>;
+
+        /// Returns `true` if the referenced object is an instance of `T`.
+        fn is_instance_of<T: BoundClass>(&self) -> bool;
+
+        /// Casts the reference to `T`, or returns it unchanged if the object isn't an instance of `T`.
+        fn try_cast<T: BoundClass>(self) -> Result<Self::Cast<T>, Self>;
+    }
+
+    fn is_instance_of<T: BoundClass>(env: Env<'_>, object: jobject) -> bool {
+        unsafe {
+            let jni_env = env.as_raw();
+            ((**jni_env).v1_1.IsInstanceOf)(jni_env, object, T::class_global_ref(env))
+        }
+    }
+
+    impl<'env, U: ReferenceType> InstanceOf<'env> for Local<'env, U> {
+        type Cast<T: BoundClass> = Local<'env, T>;
+
+        fn is_instance_of<T: BoundClass>(&self) -> bool {
+            is_instance_of::<T>(self.env(), self.as_raw())
+        }
+
+        fn try_cast<T: BoundClass>(self) -> Result<Local<'env, T>, Self> {
+            if !self.is_instance_of::<T>() {
+                return Err(self);
+            }
+            let env = self.env();
+            Ok(unsafe { Local::from_raw(env, self.into_raw()) })
+        }
+    }
+
+    impl<'env, U: ReferenceType> InstanceOf<'env> for Ref<'env, U> {
+        type Cast<T: BoundClass> = Ref<'env, T>;
+
+        fn is_instance_of<T: BoundClass>(&self) -> bool {
+            is_instance_of::<T>(self.env(), self.as_raw())
+        }
+
+        fn try_cast<T: BoundClass>(self) -> Result<Ref<'env, T>, Self> {
+            if !self.is_instance_of::<T>() {
+                return Err(self);
+            }
+            Ok(unsafe { Ref::from_raw(self.env(), self.as_raw()) })
+        }
+    }
+}
+
+/// Rust strings accepted wherever the bindings expect a `java.lang.String`.
+pub mod strings {
+    use super::lang::String as JString;
+    use std::marker::PhantomData;
+    use java_oxide::{
+        sys::{jobject, jvalue},
+        AsArg, AsJValue, Env, Global, Local, Null, Ref,
+    };
+
+    /// A `java.lang.String` argument, either created from a Rust string or borrowed for `'a` from a Java reference.
+    pub enum JavaStrArg<'env, 'a> {
+        Local(Local<'env, JString>),
+        Borrowed(jobject, PhantomData<&'a ()>),
+    }
+
+    unsafe impl AsJValue for JavaStrArg<'_, '_> {
+        fn as_jvalue(&self) -> jvalue {
+            jvalue { l: self.as_arg() }
+        }
+    }
+
+    unsafe impl AsArg<JString> for JavaStrArg<'_, '_> {
+        fn as_arg(&self) -> jobject {
+            match self {
+                JavaStrArg::Local(string) => string.as_raw(),
+                JavaStrArg::Borrowed(object, _) => *object,
+            }
+        }
+    }
+
+    /// Values that can be passed as a `java.lang.String` argument: Rust strings (copied into a new Java string), Java
+    /// string references, and `None`/[`Null`] for `null`.
+    pub trait IntoJavaStr {
+        /// The argument, which can't outlive a borrowed Java reference.
+        type Arg<'env>: AsArg<JString>;
+
+        fn into_java_str<'env>(self, env: Env<'env>) -> Self::Arg<'env>;
+    }
+
+    impl IntoJavaStr for &str {
+        type Arg<'env> = JavaStrArg<'env, 'static>;
+
+        fn into_java_str<'env>(self, env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Local(JString::from_env_str(env, self))
+        }
+    }
+
+    impl IntoJavaStr for &String {
+        type Arg<'env> = JavaStrArg<'env, 'static>;
+
+        fn into_java_str<'env>(self, env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Local(JString::from_env_str(env, self))
+        }
+    }
+
+    impl IntoJavaStr for String {
+        type Arg<'env> = JavaStrArg<'env, 'static>;
+
+        fn into_java_str<'env>(self, env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Local(JString::from_env_str(env, self))
+        }
+    }
+
+    impl<'a> IntoJavaStr for &'a Ref<'_, JString> {
+        type Arg<'env> = JavaStrArg<'env, 'a>;
+
+        fn into_java_str<'env>(self, _env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Borrowed(self.as_raw(), PhantomData)
+        }
+    }
+
+    impl<'a> IntoJavaStr for &'a Local<'_, JString> {
+        type Arg<'env> = JavaStrArg<'env, 'a>;
+
+        fn into_java_str<'env>(self, _env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Borrowed(self.as_raw(), PhantomData)
+        }
+    }
+
+    impl<'a> IntoJavaStr for &'a Global<JString> {
+        type Arg<'env> = JavaStrArg<'env, 'a>;
+
+        fn into_java_str<'env>(self, _env: Env<'env>) -> Self::Arg<'env> {
+            JavaStrArg::Borrowed(self.as_raw(), PhantomData)
+        }
+    }
+
+    impl IntoJavaStr for Null {
+        type Arg<'env> = Null;
+
+        fn into_java_str<'env>(self, _env: Env<'env>) -> Self::Arg<'env> {
+            Null
+        }
+    }
+
+    impl<T: IntoJavaStr> IntoJavaStr for Option<T> {
+        type Arg<'env> = Option<T::Arg<'env>>;
+
+        fn into_java_str<'env>(self, env: Env<'env>) -> Self::Arg<'env> {
+            self.map(|string: T| string.into_java_str(env))
+        }
+    }
+}
+
+/// Conversions between Rust primitives and their boxed `java.lang` wrapper classes.
+pub mod boxed {
+    use super::lang::Throwable;
+    use java_oxide::{
+        sys::{jobject, jvalue},
+        AsArg, AsJValue, Env, Local, Null, Ref, ReferenceType,
+    };
+
+    /// A bound wrapper class such as `java.lang.Integer`, boxing [`Boxed::Primitive`] values.
+    pub unsafe trait Boxed: ReferenceType {
+        type Primitive;
+
+        /// Boxes `value` through the wrapper's `valueOf`.
+        fn box_value<'env>(env: Env<'env>, value: Self::Primitive) -> Result<Local<'env, Self>, Local<'env, Throwable>>;
+
+        /// Unboxes through the wrapper's `xxxValue` method.
+        fn unbox_value<'env>(self: &Ref<'env, Self>) -> Result<Self::Primitive, Local<'env, Throwable>>;
+    }
+
+    /// A boxed argument, either boxed from a Rust primitive or borrowed from a Java reference.
+    pub enum BoxedArg<'env, T: Boxed> {
+        Local(Local<'env, T>),
+        Borrowed(jobject),
+    }
+
+    unsafe impl<T: Boxed> AsJValue for BoxedArg<'_, T> {
+        fn as_jvalue(&self) -> jvalue {
+            jvalue { l: self.as_arg() }
+        }
+    }
+
+    unsafe impl<T: Boxed> AsArg<T> for BoxedArg<'_, T> {
+        fn as_arg(&self) -> jobject {
+            match self {
+                BoxedArg::Local(value) => value.as_raw(),
+                BoxedArg::Borrowed(object) => *object,
+            }
+        }
+    }
+
+    /// Values that can be passed where the wrapper class `T` is expected: the matching Rust primitive, references to
+    /// `T`, and `None`/[`Null`] for `null`.
+    pub trait IntoBoxed<T: Boxed> {
+        fn into_boxed<'env>(self, env: Env<'env>) -> Result<BoxedArg<'env, T>, Local<'env, Throwable>>;
+    }
+
+    impl<T: Boxed> IntoBoxed<T> for &Ref<'_, T> {
+        fn into_boxed<'env>(self, _env: Env<'env>) -> Result<BoxedArg<'env, T>, Local<'env, Throwable>> {
+            Ok(BoxedArg::Borrowed(self.as_raw()))
+        }
+    }
+
+    impl<T: Boxed> IntoBoxed<T> for &Local<'_, T> {
+        fn into_boxed<'env>(self, _env: Env<'env>) -> Result<BoxedArg<'env, T>, Local<'env, Throwable>> {
+            Ok(BoxedArg::Borrowed(self.as_raw()))
+        }
+    }
+
+    impl<T: Boxed> IntoBoxed<T> for Null {
+        fn into_boxed<'env>(self, _env: Env<'env>) -> Result<BoxedArg<'env, T>, Local<'env, Throwable>> {
+            Ok(BoxedArg::Borrowed(std::ptr::null_mut()))
+        }
+    }
+
+    impl<T: Boxed, U: IntoBoxed<T>> IntoBoxed<T> for Option<U> {
+        fn into_boxed<'env>(self, env: Env<'env>) -> Result<BoxedArg<'env, T>, Local<'env, Throwable>> {
+            match self {
+                Some(value) => value.into_boxed(env),
+                None => Ok(BoxedArg::Borrowed(std::ptr::null_mut())),
+            }
+        }
+    }
+}
+
+/// Rust adapters for `java.lang.Iterable`, `java.util.Collection`, `List`, `Set` and `Map`, calling through JNI
+/// directly so they work whether or not the collection interfaces themselves are bound.
+pub mod collections {
+    use super::lang::{Object, Throwable};
+    use java_oxide::{
+        sys::{jmethodID, jobject},
+        AsArg, AsJValue, Env, JMethodID, Local, ReferenceType,
+    };
+    use std::{ffi::CStr, marker::PhantomData, sync::OnceLock};
+
+    pub(crate) fn method(env: Env<'_>, cache: &OnceLock<JMethodID>, class: &CStr, name: &CStr, descriptor: &CStr) -> jmethodID {
+        cache
+            .get_or_init(|| unsafe {
+                let class = Local::<Object>::from_raw(env, env.require_class(class));
+                JMethodID::from_raw(env.require_method(class.as_raw(), name, descriptor))
+            })
+            .as_raw()
+    }
+
+    fn constructor<'env>(env: Env<'env>, cache: &OnceLock<JMethodID>, class: &CStr) -> (Local<'env, Object>, jmethodID) {
+        let class = unsafe { Local::<Object>::from_raw(env, env.require_class(class)) };
+        let constructor = cache
+            .get_or_init(|| unsafe { JMethodID::from_raw(env.require_method(class.as_raw(), c"<init>", c"()V")) })
+            .as_raw();
+        (class, constructor)
+    }
+
+    /// A Rust [`Iterator`] over a `java.util.Iterator`, yielding `E` elements.
+    pub struct JavaIter<'env, E: ReferenceType> {
+        iterator: Local<'env, Object>,
+        _element: PhantomData<E>,
+    }
+
+    impl<'env, E: ReferenceType> Iterator for JavaIter<'env, E> {
+        /// Elements may be `null`, and `hasNext()`/`next()` may throw.
+        type Item = Result<Option<Local<'env, E>>, Local<'env, Throwable>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            static HAS_NEXT: OnceLock<JMethodID> = OnceLock::new();
+            static NEXT: OnceLock<JMethodID> = OnceLock::new();
+            let env = self.iterator.env();
+            let has_next = method(env, &HAS_NEXT, c"java/util/Iterator", c"hasNext", c"()Z");
+            let next = method(env, &NEXT, c"java/util/Iterator", c"next", c"()Ljava/lang/Object;");
+            unsafe {
+                match env.call_boolean_method_a::<Throwable>(self.iterator.as_raw(), has_next, [].as_ptr()) {
+                    Ok(true) => Some(env.call_object_method_a::<E, Throwable>(self.iterator.as_raw(), next, [].as_ptr())),
+                    Ok(false) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            }
+        }
+    }
+
+    /// Calls `iterator()` on a `java.lang.Iterable`.
+    pub fn iter<'env, E: ReferenceType>(env: Env<'env>, iterable: jobject) -> Result<JavaIter<'env, E>, Local<'env, Throwable>> {
+        static ITERATOR: OnceLock<JMethodID> = OnceLock::new();
+        let iterator = method(env, &ITERATOR, c"java/lang/Iterable", c"iterator", c"()Ljava/util/Iterator;");
+        let iterator = unsafe { env.call_object_method_a::<Object, Throwable>(iterable, iterator, [].as_ptr())? };
+        Ok(JavaIter {
+            iterator: iterator.expect("iterator() returned null"),
+            _element: PhantomData,
+        })
+    }
+
+    /// Calls `size()` on a `java.util.Collection`, or a `java.util.Map` if `map` is set.
+    pub fn len<'env>(env: Env<'env>, collection: jobject, map: bool) -> Result<usize, Local<'env, Throwable>> {
+        static COLLECTION_SIZE: OnceLock<JMethodID> = OnceLock::new();
+        static MAP_SIZE: OnceLock<JMethodID> = OnceLock::new();
+        let size = match map {
+            false => method(env, &COLLECTION_SIZE, c"java/util/Collection", c"size", c"()I"),
+            true => method(env, &MAP_SIZE, c"java/util/Map", c"size", c"()I"),
+        };
+        unsafe { env.call_int_method_a::<Throwable>(collection, size, [].as_ptr()) }.map(|size| size as usize)
+    }
+
+    /// Calls `get(int)` on a `java.util.List`.
+    pub fn get<'env, E: ReferenceType>(env: Env<'env>, list: jobject, index: usize) -> Result<Option<Local<'env, E>>, Local<'env, Throwable>> {
+        static GET: OnceLock<JMethodID> = OnceLock::new();
+        let get = method(env, &GET, c"java/util/List", c"get", c"(I)Ljava/lang/Object;");
+        let args = [AsJValue::as_jvalue(&(index as i32))];
+        unsafe { env.call_object_method_a::<E, Throwable>(list, get, args.as_ptr()) }
+    }
+
+    /// Builds a `java.util.ArrayList` from `elements`.
+    pub fn array_list_from_iter<'env, R: ReferenceType, E: ReferenceType>(
+        env: Env<'env>,
+        elements: impl IntoIterator<Item = impl AsArg<E>>,
+    ) -> Result<Local<'env, R>, Local<'env, Throwable>> {
+        static NEW: OnceLock<JMethodID> = OnceLock::new();
+        static ADD: OnceLock<JMethodID> = OnceLock::new();
+        let (class, new) = constructor(env, &NEW, c"java/util/ArrayList");
+        let add = method(env, &ADD, c"java/util/ArrayList", c"add", c"(Ljava/lang/Object;)Z");
+        unsafe {
+            let list = env.new_object_a::<R, Throwable>(class.as_raw(), new, [].as_ptr())?;
+            for element in elements {
+                let args = [AsJValue::as_jvalue(&element)];
+                env.call_boolean_method_a::<Throwable>(list.as_raw(), add, args.as_ptr())?;
+            }
+            Ok(list)
+        }
+    }
+
+    /// Builds a `java.util.HashMap` from `entries`.
+    pub fn hash_map_from_iter<'env, R: ReferenceType, K: ReferenceType, V: ReferenceType>(
+        env: Env<'env>,
+        entries: impl IntoIterator<Item = (impl AsArg<K>, impl AsArg<V>)>,
+    ) -> Result<Local<'env, R>, Local<'env, Throwable>> {
+        static NEW: OnceLock<JMethodID> = OnceLock::new();
+        static PUT: OnceLock<JMethodID> = OnceLock::new();
+        let (class, new) = constructor(env, &NEW, c"java/util/HashMap");
+        let put = method(env, &PUT, c"java/util/HashMap", c"put", c"(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;");
+        unsafe {
+            let map = env.new_object_a::<R, Throwable>(class.as_raw(), new, [].as_ptr())?;
+            for (key, value) in entries {
+                let args = [AsJValue::as_jvalue(&key), AsJValue::as_jvalue(&value)];
+                env.call_object_method_a::<Object, Throwable>(map.as_raw(), put, args.as_ptr())?;
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Conversions between Rust `Option`s and `java.util.Optional`, `OptionalInt`, `OptionalLong` and `OptionalDouble`.
+pub mod optionals {
+    use super::{
+        collections::method,
+        lang::{Object, Throwable},
+    };
+    use java_oxide::{
+        sys::{jobject, jvalue},
+        AsArg, AsJValue, Env, JMethodID, Local, Null, Ref, ReferenceType,
+    };
+    use std::{ffi::CStr, sync::OnceLock};
+
+    fn call_static<'env, O: ReferenceType>(
+        env: Env<'env>,
+        cache: &OnceLock<JMethodID>,
+        class: &CStr,
+        name: &CStr,
+        descriptor: &CStr,
+        args: &[jvalue],
+    ) -> Result<Local<'env, O>, Local<'env, Throwable>> {
+        unsafe {
+            let class = Local::<Object>::from_raw(env, env.require_class(class));
+            let method = cache
+                .get_or_init(|| JMethodID::from_raw(env.require_static_method(class.as_raw(), name, descriptor)))
+                .as_raw();
+            env.call_static_object_method_a::<O, Throwable>(class.as_raw(), method, args.as_ptr())
+                .map(|optional| optional.expect("Optional factories never return null"))
+        }
+    }
+
+    /// Unwraps a `java.util.Optional` through `isPresent()` and `get()`. A `null` Optional is treated as empty.
+    pub fn get<'env, T: ReferenceType>(
+        optional: Option<Local<'env, impl ReferenceType>>,
+    ) -> Result<Option<Local<'env, T>>, Local<'env, Throwable>> {
+        static IS_PRESENT: OnceLock<JMethodID> = OnceLock::new();
+        static GET: OnceLock<JMethodID> = OnceLock::new();
+        let Some(optional) = optional else {
+            return Ok(None);
+        };
+        let env = optional.env();
+        let is_present = method(env, &IS_PRESENT, c"java/util/Optional", c"isPresent", c"()Z");
+        let get = method(env, &GET, c"java/util/Optional", c"get", c"()Ljava/lang/Object;");
+        unsafe {
+            match env.call_boolean_method_a::<Throwable>(optional.as_raw(), is_present, [].as_ptr())? {
+                true => env.call_object_method_a::<T, Throwable>(optional.as_raw(), get, [].as_ptr()),
+                false => Ok(None),
+            }
+        }
+    }
+
+    /// Builds a `java.util.Optional` through `ofNullable`.
+    pub fn of<'env, O: ReferenceType, T: ReferenceType>(
+        env: Env<'env>,
+        value: Option<impl AsArg<T>>,
+    ) -> Result<Local<'env, O>, Local<'env, Throwable>> {
+        static OF_NULLABLE: OnceLock<JMethodID> = OnceLock::new();
+        let args = [jvalue {
+            l: value.as_ref().map_or(std::ptr::null_mut(), AsArg::as_arg),
+        }];
+        call_static(
+            env,
+            &OF_NULLABLE,
+            c"java/util/Optional",
+            c"ofNullable",
+            c"(Ljava/lang/Object;)Ljava/util/Optional;",
+            &args,
+        )
+    }
+
+    macro_rules! primitive_optional {
+        ($get:ident, $of:ident, $primitive:ty, $call:ident, $name:literal, $class:literal, $getter:literal, $getter_name:literal, $getter_descriptor:literal, $of_descriptor:literal, $empty_descriptor:literal) => {
+            #[doc = concat!("Unwraps a `", $name, "` through `isPresent()` and `", $getter_name, "()`. A `null` Optional is treated as empty.")]
+            pub fn $get<'env>(
+                optional: Option<Local<'env, impl ReferenceType>>,
+            ) -> Result<Option<$primitive>, Local<'env, Throwable>> {
+                static IS_PRESENT: OnceLock<JMethodID> = OnceLock::new();
+                static GET: OnceLock<JMethodID> = OnceLock::new();
+                let Some(optional) = optional else {
+                    return Ok(None);
+                };
+                let env = optional.env();
+                let is_present = method(env, &IS_PRESENT, $class, c"isPresent", c"()Z");
+                let get = method(env, &GET, $class, $getter, $getter_descriptor);
+                unsafe {
+                    match env.call_boolean_method_a::<Throwable>(optional.as_raw(), is_present, [].as_ptr())? {
+                        true => env.$call::<Throwable>(optional.as_raw(), get, [].as_ptr()).map(Some),
+                        false => Ok(None),
                     }
                 }
             }
 
-            // Consider also dumping:
-            // API level 1+:
-            //      getCause()
-            // API level 19+:
-            //      getSuppressed()
+            #[doc = concat!("Builds a `", $name, "` through `of` or `empty`.")]
+            pub fn $of<'env, O: ReferenceType>(env: Env<'env>, value: Option<$primitive>) -> Result<Local<'env, O>, Local<'env, Throwable>> {
+                static OF: OnceLock<JMethodID> = OnceLock::new();
+                static EMPTY: OnceLock<JMethodID> = OnceLock::new();
+                match value {
+                    Some(value) => call_static(env, &OF, $class, c"of", $of_descriptor, &[value.as_jvalue()]),
+                    None => call_static(env, &EMPTY, $class, c"empty", $empty_descriptor, &[]),
+                }
+            }
+        };
+    }
+
+    primitive_optional!(get_int, of_int, i32, call_int_method_a, "java.util.OptionalInt", c"java/util/OptionalInt", c"getAsInt", "getAsInt", c"()I", c"(I)Ljava/util/OptionalInt;", c"()Ljava/util/OptionalInt;");
+    primitive_optional!(get_long, of_long, i64, call_long_method_a, "java.util.OptionalLong", c"java/util/OptionalLong", c"getAsLong", "getAsLong", c"()J", c"(J)Ljava/util/OptionalLong;", c"()Ljava/util/OptionalLong;");
+    primitive_optional!(get_double, of_double, f64, call_double_method_a, "java.util.OptionalDouble", c"java/util/OptionalDouble", c"getAsDouble", "getAsDouble", c"()D", c"(D)Ljava/util/OptionalDouble;", c"()Ljava/util/OptionalDouble;");
 
-            Ok(())
+    /// An Optional argument, either built from a Rust `Option` or borrowed from a Java reference.
+    pub enum OptionalArg<'env, O: ReferenceType> {
+        Local(Local<'env, O>),
+        Borrowed(jobject),
+    }
+
+    unsafe impl<O: ReferenceType> AsJValue for OptionalArg<'_, O> {
+        fn as_jvalue(&self) -> jvalue {
+            jvalue { l: self.as_arg() }
         }
     }
 
-    impl JString {
-        /// Create new local string from an Env + AsRef<str>
-        pub fn from_env_str<'env, S: AsRef<str>>(env: Env<'env>, string: S) -> Local<'env, Self> {
-            let chars = string.as_ref().encode_utf16().collect::<Vec<_>>();
+    unsafe impl<O: ReferenceType> AsArg<O> for OptionalArg<'_, O> {
+        fn as_arg(&self) -> jobject {
+            match self {
+                OptionalArg::Local(optional) => optional.as_raw(),
+                OptionalArg::Borrowed(object) => *object,
+            }
+        }
+    }
+
+    /// Values that can be passed where the Optional class `O` holding `T` elements is expected: a Rust `Option`
+    /// (through impls generated alongside each bound Optional class), references to `O`, and [`Null`].
+    pub trait IntoOptional<O: ReferenceType, T = ()> {
+        fn into_optional<'env>(self, env: Env<'env>) -> Result<OptionalArg<'env, O>, Local<'env, Throwable>>;
+    }
+
+    impl<O: ReferenceType, T> IntoOptional<O, T> for &Ref<'_, O> {
+        fn into_optional<'env>(self, _env: Env<'env>) -> Result<OptionalArg<'env, O>, Local<'env, Throwable>> {
+            Ok(OptionalArg::Borrowed(self.as_raw()))
+        }
+    }
 
-            let string = unsafe { env.new_string(chars.as_ptr(), chars.len() as jsize) };
-            unsafe { Local::from_raw(env, string) }
+    impl<O: ReferenceType, T> IntoOptional<O, T> for &Local<'_, O> {
+        fn into_optional<'env>(self, _env: Env<'env>) -> Result<OptionalArg<'env, O>, Local<'env, Throwable>> {
+            Ok(OptionalArg::Borrowed(self.as_raw()))
         }
+    }
 
-        fn string_chars<'env>(self: &Ref<'env, Self>) -> StringChars<'env> {
-            unsafe { StringChars::from_env_jstring(self.env(), self.as_raw()) }
+    impl<O: ReferenceType, T> IntoOptional<O, T> for Null {
+        fn into_optional<'env>(self, _env: Env<'env>) -> Result<OptionalArg<'env, O>, Local<'env, Throwable>> {
+            Ok(OptionalArg::Borrowed(std::ptr::null_mut()))
         }
+    }
+}
 
-        /// Returns a new [Ok]\([String]\), or an [Err]\([DecodeUtf16Error]\) if if it contained any invalid UTF16.
-        ///
-        /// [Ok]:                       https://doc.rust-lang.org/std/result/enum.Result.html#variant.Ok
-        /// [Err]:                      https://doc.rust-lang.org/std/result/enum.Result.html#variant.Err
-        /// [DecodeUtf16Error]:         https://doc.rust-lang.org/std/char/struct.DecodeUtf16Error.html
-        /// [String]:                   https://doc.rust-lang.org/std/string/struct.String.html
-        /// [REPLACEMENT_CHARACTER]:    https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
-        pub fn to_string(self: &Ref<'_, Self>) -> Result<String, DecodeUtf16Error> {
-            self.string_chars().to_string()
+/// Rust std traits implemented through the contracts of `java.lang.Object`, `Comparable` and `AutoCloseable`.
+pub mod contracts {
+    use super::{
+        collections::method,
+        lang::{String as JString, Throwable},
+    };
+    use java_oxide::{AsJValue, Env, Local, Ref, ReferenceType};
+    use std::{cmp::Ordering, fmt, hash, ops::Deref, sync::OnceLock};
+
+    /// Bound classes implementing `java.lang.Comparable`, ordered by [`JavaObject`] through `compareTo`.
+    pub unsafe trait Comparable: ReferenceType {}
+
+    /// Bound classes implementing `java.lang.AutoCloseable`, which can be wrapped in a [`CloseGuard`].
+    pub unsafe trait AutoCloseable: ReferenceType {}
+
+    /// A reference whose Rust std traits go through its Java contracts: [`fmt::Display`] through `toString()`,
+    /// [`PartialEq`]/[`Eq`] through `equals()`, [`Hash`](hash::Hash) through `hashCode()`, and
+    /// [`PartialOrd`]/[`Ord`] through `compareTo()` for [`Comparable`] classes.
+    ///
+    /// The std traits can't report Java exceptions, so `equals()`, `hashCode()` and `compareTo()` panic if they throw,
+    /// and `toString()` fails with [`fmt::Error`].
+    pub struct JavaObject<'env, T: ReferenceType>(pub Ref<'env, T>);
+
+    // Not derived, which would require `T: Copy` from the uninhabited binding types
+    impl<'env, T: ReferenceType> Clone for JavaObject<'env, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<'env, T: ReferenceType> Copy for JavaObject<'env, T> {}
+
+    impl<'env, T: ReferenceType> JavaObject<'env, T> {
+        pub fn new(object: &Ref<'env, T>) -> Self {
+            Self(*object)
+        }
+    }
+
+    impl<'env, T: ReferenceType> Deref for JavaObject<'env, T> {
+        type Target = Ref<'env, T>;
+
+        fn deref(&self) -> &Ref<'env, T> {
+            &self.0
+        }
+    }
+
+    impl<T: ReferenceType> fmt::Display for JavaObject<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            static TO_STRING: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+            let env = self.0.env();
+            let to_string = method(env, &TO_STRING, c"java/lang/Object", c"toString", c"()Ljava/lang/String;");
+            match unsafe { env.call_object_method_a::<JString, Throwable>(self.0.as_raw(), to_string, [].as_ptr()) } {
+                Ok(Some(string)) => f.write_str(&string.to_string_lossy()),
+                Ok(None) => f.write_str("null"),
+                Err(_) => Err(fmt::Error),
+            }
+        }
+    }
+
+    impl<T: ReferenceType, U: ReferenceType> PartialEq<JavaObject<'_, U>> for JavaObject<'_, T> {
+        fn eq(&self, other: &JavaObject<'_, U>) -> bool {
+            static EQUALS: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+            let env = self.0.env();
+            let equals = method(env, &EQUALS, c"java/lang/Object", c"equals", c"(Ljava/lang/Object;)Z");
+            let args = [other.0.as_jvalue()];
+            unsafe { env.call_boolean_method_a::<Throwable>(self.0.as_raw(), equals, args.as_ptr()) }
+                .unwrap_or_else(|_| panic!("equals() threw an exception"))
+        }
+    }
+
+    impl<T: ReferenceType> Eq for JavaObject<'_, T> {}
+
+    impl<T: ReferenceType> hash::Hash for JavaObject<'_, T> {
+        fn hash<H: hash::Hasher>(&self, state: &mut H) {
+            static HASH_CODE: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+            let env = self.0.env();
+            let hash_code = method(env, &HASH_CODE, c"java/lang/Object", c"hashCode", c"()I");
+            let hash = unsafe { env.call_int_method_a::<Throwable>(self.0.as_raw(), hash_code, [].as_ptr()) }
+                .unwrap_or_else(|_| panic!("hashCode() threw an exception"));
+            state.write_i32(hash);
+        }
+    }
+
+    impl<T: Comparable> PartialOrd for JavaObject<'_, T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<T: Comparable> Ord for JavaObject<'_, T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            static COMPARE_TO: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+            let env = self.0.env();
+            let compare_to = method(env, &COMPARE_TO, c"java/lang/Comparable", c"compareTo", c"(Ljava/lang/Object;)I");
+            let args = [other.0.as_jvalue()];
+            unsafe { env.call_int_method_a::<Throwable>(self.0.as_raw(), compare_to, args.as_ptr()) }
+                .unwrap_or_else(|_| panic!("compareTo() threw an exception"))
+                .cmp(&0)
+        }
+    }
+
+    /// Owns a local reference to an [`AutoCloseable`] and calls `close()` when dropped.
+    ///
+    /// Exceptions thrown when closing on drop are discarded. Use [`CloseGuard::close`] to observe them.
+    pub struct CloseGuard<'env, T: AutoCloseable>(Option<Local<'env, T>>);
+
+    impl<'env, T: AutoCloseable> CloseGuard<'env, T> {
+        pub fn new(resource: Local<'env, T>) -> Self {
+            Self(Some(resource))
+        }
+
+        /// Calls `close()` now, returning any exception it threw.
+        pub fn close(mut self) -> Result<(), Local<'env, Throwable>> {
+            match self.0.take() {
+                Some(resource) => close(resource.env(), &resource),
+                None => Ok(()),
+            }
+        }
+
+        /// Gives back the resource without closing it.
+        pub fn into_inner(mut self) -> Local<'env, T> {
+            self.0.take().expect("only taken when consuming the guard")
+        }
+    }
+
+    impl<'env, T: AutoCloseable> Deref for CloseGuard<'env, T> {
+        type Target = Local<'env, T>;
+
+        fn deref(&self) -> &Local<'env, T> {
+            self.0.as_ref().expect("only taken when consuming the guard")
+        }
+    }
+
+    impl<T: AutoCloseable> Drop for CloseGuard<'_, T> {
+        fn drop(&mut self) {
+            if let Some(resource) = self.0.take() {
+                let _ = close(resource.env(), &resource);
+            }
+        }
+    }
+
+    fn close<'env>(env: Env<'env>, resource: &Ref<'_, impl AutoCloseable>) -> Result<(), Local<'env, Throwable>> {
+        static CLOSE: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+        let close = method(env, &CLOSE, c"java/lang/AutoCloseable", c"close", c"()V");
+        unsafe { env.call_void_method_a::<Throwable>(resource.as_raw(), close, [].as_ptr()) }
+    }
+}
+
+/// Support for the `{Class}Proxy` traits implemented by Java proxy classes.
+pub mod proxies {
+    use std::cell::Cell;
+
+    thread_local! {
+        static JAVA_DEFAULT: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Makes the Java proxy run the overridden Java implementation of the method being called once the trait method
+    /// returns, discarding its return value. This is the default body of the trait methods overriding default or
+    /// concrete Java methods.
+    pub fn use_java_default() {
+        JAVA_DEFAULT.with(|java_default| java_default.set(true));
+    }
+
+    /// Calls a trait method through `f`, returning its result and whether it asked for the Java implementation.
+    #[doc(hidden)]
+    pub fn dispatch<R>(f: impl FnOnce() -> R) -> (R, bool) {
+        let outer = JAVA_DEFAULT.with(|java_default| java_default.replace(false));
+        let result = f();
+        (result, JAVA_DEFAULT.with(|java_default| java_default.replace(outer)))
+    }
+}
+
+/// Rust [`Future`](std::future::Future)s completed by `java.util.concurrent.CompletionStage`s.
+pub mod futures {
+    use super::{
+        collections::method,
+        lang::{Object, Throwable},
+    };
+    use java_oxide::{AsJValue, Global, Local, Ref, ReferenceType};
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex, OnceLock},
+        task::{Context, Poll, Waker},
+    };
+
+    /// The value a `CompletionStage` completed with, or the exception it completed exceptionally with.
+    pub type Outcome<T> = Result<Option<Global<T>>, Global<Throwable>>;
+
+    struct State<T: ReferenceType> {
+        outcome: Option<Outcome<T>>,
+        waker: Option<Waker>,
+    }
+
+    /// A future resolving once the `CompletionStage` it was created from completes.
+    ///
+    /// The outcome holds global references, so the future can be awaited from any thread.
+    pub struct JavaFuture<T: ReferenceType> {
+        state: Arc<Mutex<State<T>>>,
+    }
+
+    /// The completing side of a [`JavaFuture`], registered with `whenComplete` as a `java.util.function.BiConsumer`
+    /// proxy.
+    pub struct Completion<T: ReferenceType> {
+        state: Arc<Mutex<State<T>>>,
+    }
+
+    impl<T: ReferenceType> JavaFuture<T> {
+        pub fn new() -> (Self, Completion<T>) {
+            let state = Arc::new(Mutex::new(State {
+                outcome: None,
+                waker: None,
+            }));
+            (Self { state: state.clone() }, Completion { state })
         }
+    }
+
+    impl<T: ReferenceType> Future for JavaFuture<T> {
+        type Output = Outcome<T>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Outcome<T>> {
+            let mut state = self.state.lock().unwrap();
+            match state.outcome.take() {
+                Some(outcome) => Poll::Ready(outcome),
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
 
-        /// Returns a new [String] with any invalid UTF16 characters replaced with [REPLACEMENT_CHARACTER]s (`'\u{FFFD}'`.)
+    impl<T: ReferenceType> Completion<T> {
+        /// Completes the future with the `(value, exception)` pair passed to `whenComplete` callbacks.
         ///
-        /// [String]:                   https://doc.rust-lang.org/std/string/struct.String.html
-        /// [REPLACEMENT_CHARACTER]:    https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
-        pub fn to_string_lossy(self: &Ref<'_, Self>) -> String {
-            self.string_chars().to_string_lossy()
+        /// Panics if `value` isn't a `T`, which the generic signature the future was typed from promised.
+        pub fn complete(&self, value: Option<Ref<'_, Object>>, exception: Option<Ref<'_, Object>>) {
+            let outcome = match exception {
+                Some(exception) => Err(exception
+                    .cast::<Throwable>()
+                    .unwrap_or_else(|_| panic!("whenComplete exception isn't a Throwable"))
+                    .as_global()),
+                None => Ok(value.map(|value| {
+                    value
+                        .cast::<T>()
+                        .unwrap_or_else(|_| panic!("CompletionStage value doesn't match its generic signature"))
+                        .as_global()
+                })),
+            };
+            let mut state = self.state.lock().unwrap();
+            state.outcome = Some(outcome);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Registers `consumer`, a `java.util.function.BiConsumer`, with `stage.whenComplete`.
+    pub fn when_complete<'env>(
+        stage: &Ref<'env, impl ReferenceType>,
+        consumer: &Ref<'env, impl ReferenceType>,
+    ) -> Result<(), Local<'env, Throwable>> {
+        static WHEN_COMPLETE: OnceLock<java_oxide::JMethodID> = OnceLock::new();
+        let env = stage.env();
+        let when_complete = method(
+            env,
+            &WHEN_COMPLETE,
+            c"java/util/concurrent/CompletionStage",
+            c"whenComplete",
+            c"(Ljava/util/function/BiConsumer;)Ljava/util/concurrent/CompletionStage;",
+        );
+        let args = [consumer.as_jvalue()];
+        unsafe { env.call_object_method_a::<Object, Throwable>(stage.as_raw(), when_complete, args.as_ptr()) }.map(|_| ())
+    }
+}
+
+/// Fallible resolution of classes and members the running JVM may lack, used by `try_` and `is_available` companions.
+pub mod lookup {
+    use super::lang::Object;
+    use java_oxide::{
+        sys::{jclass, jfieldID, jmethodID, jobject},
+        Env, Global, JFieldID, JMethodID, Local,
+    };
+    use std::{error::Error, ffi::CStr, fmt, sync::OnceLock};
+
+    /// A class, method or field missing from the running JVM.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct MissingMember {
+        /// JNI path of the class, such as `java/lang/Object`.
+        pub class: &'static CStr,
+        /// Name and descriptor of the missing member, `None` if the class itself is missing.
+        pub member: Option<(&'static CStr, &'static CStr)>,
+    }
+
+    impl fmt::Display for MissingMember {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let class = self.class.to_string_lossy();
+            match self.member {
+                None => write!(f, "missing class {class}"),
+                Some((name, descriptor)) => write!(
+                    f,
+                    "missing member {class}.{}{}",
+                    name.to_string_lossy(),
+                    descriptor.to_string_lossy()
+                ),
+            }
         }
     }
 
-    // OsString doesn't implement Display, so neither does java::lang::String.
-    impl JavaDebug for JString {
-        fn fmt(self: &Ref<'_, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            fmt::Debug::fmt(&self.to_string_lossy(), f) // FIXME: Unneccessary alloc? Shouldn't use lossy here?
+    impl Error for MissingMember {}
+
+    /// Finds `class` through `FindClass`, clearing the pending `NoClassDefFoundError` if it's missing.
+    pub fn find_class<'env>(env: Env<'env>, class: &CStr) -> Option<Local<'env, Object>> {
+        unsafe {
+            let raw = env.as_raw();
+            let found = ((**raw).v1_1.FindClass)(raw, class.as_ptr());
+            if found.is_null() {
+                ((**raw).v1_1.ExceptionClear)(raw);
+                return None;
+            }
+            Some(Local::from_raw(env, found))
         }
     }
 
-    impl ThrowableType for Throwable {}
+    /// Finds a method of `class` through `GetMethodID` or `GetStaticMethodID`, clearing the pending
+    /// `NoSuchMethodError` if it's missing.
+    pub fn find_method(
+        env: Env<'_>,
+        class: jclass,
+        name: &CStr,
+        descriptor: &CStr,
+        is_static: bool,
+    ) -> Option<JMethodID> {
+        unsafe {
+            let raw = env.as_raw();
+            let get_method_id = match is_static {
+                false => (**raw).v1_1.GetMethodID,
+                true => (**raw).v1_1.GetStaticMethodID,
+            };
+            let method = get_method_id(raw, class, name.as_ptr(), descriptor.as_ptr());
+            if method.is_null() {
+                ((**raw).v1_1.ExceptionClear)(raw);
+                return None;
+            }
+            Some(JMethodID::from_raw(method))
+        }
+    }
+
+    /// Finds a field of `class` through `GetFieldID` or `GetStaticFieldID`, clearing the pending
+    /// `NoSuchFieldError` if it's missing.
+    pub fn find_field(
+        env: Env<'_>,
+        class: jclass,
+        name: &CStr,
+        descriptor: &CStr,
+        is_static: bool,
+    ) -> Option<JFieldID> {
+        unsafe {
+            let raw = env.as_raw();
+            let get_field_id = match is_static {
+                false => (**raw).v1_1.GetFieldID,
+                true => (**raw).v1_1.GetStaticFieldID,
+            };
+            let field = get_field_id(raw, class, name.as_ptr(), descriptor.as_ptr());
+            if field.is_null() {
+                ((**raw).v1_1.ExceptionClear)(raw);
+                return None;
+            }
+            Some(JFieldID::from_raw(field))
+        }
+    }
+
+    /// Resolves `class` through [`find_class`], caching the outcome in `cache`.
+    pub fn class(
+        env: Env<'_>,
+        cache: &'static OnceLock<Option<Global<Object>>>,
+        class: &'static CStr,
+    ) -> Result<jobject, MissingMember> {
+        cache
+            .get_or_init(|| find_class(env, class).map(|class| class.as_global()))
+            .as_ref()
+            .map(|class| class.as_raw())
+            .ok_or(MissingMember { class, member: None })
+    }
+
+    /// Resolves a method of `class` (named `class_name`) through [`find_method`], caching the outcome in `cache`.
+    pub fn method(
+        env: Env<'_>,
+        cache: &OnceLock<Option<JMethodID>>,
+        class: jclass,
+        class_name: &'static CStr,
+        name: &'static CStr,
+        descriptor: &'static CStr,
+        is_static: bool,
+    ) -> Result<jmethodID, MissingMember> {
+        cache
+            .get_or_init(|| find_method(env, class, name, descriptor, is_static))
+            .map(|method| method.as_raw())
+            .ok_or(MissingMember {
+                class: class_name,
+                member: Some((name, descriptor)),
+            })
+    }
+
+    /// Resolves a field of `class` (named `class_name`) through [`find_field`], caching the outcome in `cache`.
+    pub fn field(
+        env: Env<'_>,
+        cache: &OnceLock<Option<JFieldID>>,
+        class: jclass,
+        class_name: &'static CStr,
+        name: &'static CStr,
+        descriptor: &'static CStr,
+        is_static: bool,
+    ) -> Result<jfieldID, MissingMember> {
+        cache
+            .get_or_init(|| find_field(env, class, name, descriptor, is_static))
+            .map(|field| field.as_raw())
+            .ok_or(MissingMember {
+                class: class_name,
+                member: Some((name, descriptor)),
+            })
+    }
+}
+
+/// Support for `validate_bindings()`, checking the bindings against the running JVM.
+pub mod validation {
+    use super::{
+        lang::Object,
+        lookup::{find_class, find_field, find_method, MissingMember},
+    };
+    use java_oxide::{sys::JNINativeMethod, Env, Local};
+    use std::{ffi::CStr, fmt, os::raw::c_void};
+
+    /// Every class and member of the bindings missing from the running JVM, as collected by `validate_bindings()`.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Report {
+        pub missing: Vec<MissingMember>,
+    }
+
+    impl Report {
+        /// Whether every class and member was found.
+        pub fn is_ok(&self) -> bool {
+            self.missing.is_empty()
+        }
+
+        /// Records `class` as missing unless `FindClass` finds it.
+        pub fn class<'env>(&mut self, env: Env<'env>, class: &'static CStr) -> Option<Local<'env, Object>> {
+            let found = find_class(env, class);
+            if found.is_none() {
+                self.missing.push(MissingMember { class, member: None });
+            }
+            found
+        }
+
+        /// Records a method of `class` (named `class_name`) as missing unless it's found.
+        pub fn method(
+            &mut self,
+            env: Env<'_>,
+            class: &Local<'_, Object>,
+            class_name: &'static CStr,
+            name: &'static CStr,
+            descriptor: &'static CStr,
+            is_static: bool,
+        ) {
+            if find_method(env, class.as_raw(), name, descriptor, is_static).is_none() {
+                self.missing.push(MissingMember {
+                    class: class_name,
+                    member: Some((name, descriptor)),
+                });
+            }
+        }
+
+        /// Records a field of `class` (named `class_name`) as missing unless it's found.
+        pub fn field(
+            &mut self,
+            env: Env<'_>,
+            class: &Local<'_, Object>,
+            class_name: &'static CStr,
+            name: &'static CStr,
+            descriptor: &'static CStr,
+            is_static: bool,
+        ) {
+            if find_field(env, class.as_raw(), name, descriptor, is_static).is_none() {
+                self.missing.push(MissingMember {
+                    class: class_name,
+                    member: Some((name, descriptor)),
+                });
+            }
+        }
+
+        /// Registers the `native` methods of a proxy `class` (named `class_name`) with `RegisterNatives`, recording
+        /// those the class doesn't declare.
+        pub fn natives(
+            &mut self,
+            env: Env<'_>,
+            class: &Local<'_, Object>,
+            class_name: &'static CStr,
+            natives: &[(&'static CStr, &'static CStr, *mut c_void)],
+        ) {
+            for &(name, descriptor, function) in natives {
+                let method = JNINativeMethod {
+                    name: name.as_ptr().cast_mut(),
+                    signature: descriptor.as_ptr().cast_mut(),
+                    fnPtr: function,
+                };
+                unsafe {
+                    let raw = env.as_raw();
+                    if ((**raw).v1_1.RegisterNatives)(raw, class.as_raw(), &method, 1) != 0 {
+                        ((**raw).v1_1.ExceptionClear)(raw);
+                        self.missing.push(MissingMember {
+                            class: class_name,
+                            member: Some((name, descriptor)),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    impl fmt::Display for Report {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.missing.as_slice() {
+                [] => write!(f, "every binding was found"),
+                missing => {
+                    write!(f, "{} bindings are missing:", missing.len())?;
+                    for member in missing {
+                        write!(f, "\n    {member}")?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
 }
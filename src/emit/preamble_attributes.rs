@@ -0,0 +1,9 @@
+#![feature(arbitrary_self_types)] // Enable arbitrary self types for smart pointers
+// #![allow(unused_imports)]
+#![allow(non_camel_case_types)] // We map Java inner classes to Outer_Inner
+#![allow(dead_code)] // We generate structs for private Java types too, just in case.
+#![allow(deprecated)] // We're generating deprecated types/methods
+#![allow(non_upper_case_globals)] // We might be generating Java style fields/methods
+#![allow(non_snake_case)] // We might be generating Java style fields/methods
+#![allow(clippy::all)] // we don't ensure generated bindings are clippy-compliant at all.
+#![allow(unsafe_code)] // play nice if user has `deny(unsafe_code)` in their crate.
@@ -0,0 +1,115 @@
+/// Macros the bindings expand to when `sources.macros` is enabled, behaving exactly like the expanded form.
+///
+/// Each caches the method or field ID in a `static`, looked up through `Self::__class_global_ref`.
+pub mod macros {
+    #![allow(unused_macros, unused_imports)] // Unused when no binding needs them
+
+    /// Calls a method, as `call_method!(new(env), ...)` for constructors, `call_method!(static
+    /// call_static_int_method_a(env), ...)` for static methods and `call_method!(call_int_method_a(env, this), ...)`
+    /// otherwise.
+    macro_rules! call_method {
+        (new($env:expr), $name:literal, $descriptor:literal, [$($arg:expr),*]) => {
+            unsafe {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                let __jni_args = [$(::java_oxide::AsJValue::as_jvalue(&$arg)),*];
+                let __jni_class = Self::__class_global_ref($env);
+                let __jni_method = __METHOD
+                    .get_or_init(|| ::java_oxide::JMethodID::from_raw($env.require_method(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.new_object_a(__jni_class, __jni_method, __jni_args.as_ptr())
+            }
+        };
+        (static $call:ident($env:expr), $name:literal, $descriptor:literal, [$($arg:expr),*]) => {
+            unsafe {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                let __jni_args = [$(::java_oxide::AsJValue::as_jvalue(&$arg)),*];
+                let __jni_class = Self::__class_global_ref($env);
+                let __jni_method = __METHOD
+                    .get_or_init(|| ::java_oxide::JMethodID::from_raw($env.require_static_method(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$call(__jni_class, __jni_method, __jni_args.as_ptr())
+            }
+        };
+        ($call:ident($env:expr, $this:expr), $name:literal, $descriptor:literal, [$($arg:expr),*]) => {
+            unsafe {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                let __jni_args = [$(::java_oxide::AsJValue::as_jvalue(&$arg)),*];
+                let __jni_class = Self::__class_global_ref($env);
+                let __jni_method = __METHOD
+                    .get_or_init(|| ::java_oxide::JMethodID::from_raw($env.require_method(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$call($this.as_raw(), __jni_method, __jni_args.as_ptr())
+            }
+        };
+    }
+
+    /// Calls this class' implementation of a method through the raw JNI function `$call`, as
+    /// `call_nonvirtual_method!(CallNonvirtualIntMethodA(env, this), ...)`, mapping the raw result with `$map`.
+    macro_rules! call_nonvirtual_method {
+        ($call:ident($env:expr, $this:expr), $name:literal, $descriptor:literal, [$($arg:expr),*], $throwable:ty, |$result:ident| $map:expr) => {
+            unsafe {
+                static __METHOD: ::std::sync::OnceLock<::java_oxide::JMethodID> = ::std::sync::OnceLock::new();
+                let __jni_args = [$(::java_oxide::AsJValue::as_jvalue(&$arg)),*];
+                let __jni_class = Self::__class_global_ref($env);
+                let __jni_method = __METHOD
+                    .get_or_init(|| ::java_oxide::JMethodID::from_raw($env.require_method(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                let __jni_raw_env = $env.as_raw();
+                let $result = ((**__jni_raw_env).v1_1.$call)(__jni_raw_env, $this.as_raw(), __jni_class, __jni_method, __jni_args.as_ptr());
+                $env.exception_check::<$throwable>().map(|()| $map)
+            }
+        };
+    }
+
+    /// Reads a field, as `get_field!(static get_static_int_field(env), ...)` or `get_field!(get_int_field(env, this),
+    /// ...)`.
+    macro_rules! get_field {
+        (static $get:ident($env:expr), $name:literal, $descriptor:literal) => {{
+            static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+            let __jni_class = Self::__class_global_ref($env);
+            unsafe {
+                let __jni_field = __FIELD
+                    .get_or_init(|| ::java_oxide::JFieldID::from_raw($env.require_static_field(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$get(__jni_class, __jni_field)
+            }
+        }};
+        ($get:ident($env:expr, $this:expr), $name:literal, $descriptor:literal) => {{
+            static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+            let __jni_class = Self::__class_global_ref($env);
+            unsafe {
+                let __jni_field = __FIELD
+                    .get_or_init(|| ::java_oxide::JFieldID::from_raw($env.require_field(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$get($this.as_raw(), __jni_field)
+            }
+        }};
+    }
+
+    /// Writes a field, as `set_field!(static set_static_int_field(env), ..., value)` or
+    /// `set_field!(set_int_field(env, this), ..., value)`.
+    macro_rules! set_field {
+        (static $set:ident($env:expr), $name:literal, $descriptor:literal, $value:expr) => {{
+            static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+            let __jni_class = Self::__class_global_ref($env);
+            unsafe {
+                let __jni_field = __FIELD
+                    .get_or_init(|| ::java_oxide::JFieldID::from_raw($env.require_static_field(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$set(__jni_class, __jni_field, $value);
+            }
+        }};
+        ($set:ident($env:expr, $this:expr), $name:literal, $descriptor:literal, $value:expr) => {{
+            static __FIELD: ::std::sync::OnceLock<::java_oxide::JFieldID> = ::std::sync::OnceLock::new();
+            let __jni_class = Self::__class_global_ref($env);
+            unsafe {
+                let __jni_field = __FIELD
+                    .get_or_init(|| ::java_oxide::JFieldID::from_raw($env.require_field(__jni_class, $name, $descriptor)))
+                    .as_raw();
+                $env.$set($this.as_raw(), __jni_field, $value);
+            }
+        }};
+    }
+
+    pub(crate) use {call_method, call_nonvirtual_method, get_field, set_field};
+}
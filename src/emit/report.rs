@@ -0,0 +1,153 @@
+use super::Context;
+use crate::{
+    manifest::{ManifestClass, ManifestMember, ManifestSkipped},
+    report::{MissingClass, Rejection, Report, Skipped},
+};
+use std::collections::HashMap;
+
+impl Context<'_> {
+    /// Collects the members of the classes written so far which couldn't be emitted and the variants which weren't,
+    /// from what `Class::write` recorded for the manifest, see [`Report`].
+    pub fn report(&self) -> Report {
+        let mut report: Report = Report::default();
+        for class in self.records.borrow().values() {
+            report_rejections(class, &mut report.rejected);
+            report_skipped(class, &mut report.skipped);
+        }
+
+        let mut blocks: HashMap<&str, usize> = HashMap::new();
+        for rejection in &report.rejected {
+            for missing in &rejection.missing_classes {
+                *blocks.entry(missing).or_default() += 1;
+            }
+        }
+        let mut missing_classes: Vec<MissingClass> = blocks
+            .into_iter()
+            .map(|(java, blocks)| MissingClass {
+                java: java.to_owned(),
+                blocks,
+            })
+            .collect();
+        missing_classes.sort_by(|a, b| b.blocks.cmp(&a.blocks).then_with(|| a.java.cmp(&b.java)));
+        report.missing_classes = missing_classes;
+
+        report
+    }
+}
+
+fn report_rejections(class: &ManifestClass, rejected: &mut Vec<Rejection>) {
+    for member in &class.members {
+        let ManifestMember {
+            java,
+            descriptor,
+            rejected: reason,
+            missing_classes,
+            ..
+        } = member;
+        let reasons: Vec<String> = reason
+            .iter()
+            .flat_map(|r| r.lines())
+            .map(str::to_owned)
+            .collect();
+        push_rejection(
+            rejected,
+            &class.java,
+            Some((java, descriptor)),
+            reasons,
+            missing_classes,
+        );
+    }
+    if let Some(proxy) = &class.proxy
+        && let Some(reason) = &proxy.rejected
+    {
+        let reasons: Vec<String> = reason.lines().map(str::to_owned).collect();
+        push_rejection(rejected, &class.java, None, reasons, &proxy.missing_classes);
+    }
+}
+
+fn report_skipped(class: &ManifestClass, skipped: &mut Vec<Skipped>) {
+    for member in &class.members {
+        skipped.extend(member.skipped.iter().map(|s: &ManifestSkipped| Skipped {
+            class: class.java.clone(),
+            member: member.java.clone(),
+            descriptor: member.descriptor.clone(),
+            name: s.name.clone(),
+            reason: s.reason.clone(),
+        }));
+    }
+}
+
+/// Records a rejection unless all of its reasons are expected, which unlike errors aren't prefixed by "ERROR".
+fn push_rejection(
+    rejected: &mut Vec<Rejection>,
+    class: &str,
+    member: Option<(&String, &String)>,
+    reasons: Vec<String>,
+    missing: &[String],
+) {
+    if !reasons
+        .iter()
+        .any(|reason: &String| reason.starts_with("ERROR"))
+    {
+        return;
+    }
+    let (member, descriptor) = member.unzip();
+    rejected.push(Rejection {
+        class: class.to_owned(),
+        member: member.cloned(),
+        descriptor: descriptor.cloned(),
+        reasons,
+        missing_classes: missing.to_vec(),
+    });
+}
+
+#[test]
+fn report_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["java/**", "test/**"]
+        bind = true
+        "#,
+        [
+            ClassBuilder::class("java/lang/Object"),
+            ClassBuilder::class("java/lang/String"),
+            ClassBuilder::class("test/A")
+                .generic_method(
+                    "find",
+                    "(Ltest/Missing;)Ljava/util/Optional;",
+                    "(Ltest/Missing;)Ljava/util/Optional<Ltest/Other;>;",
+                )
+                .method("name", "()Ljava/lang/String;")
+                .method("name_string", "()V"),
+        ],
+    );
+    let context: Context = fixture.context();
+    context.all_classes["test/A"].write(&context, None).unwrap();
+
+    let report: Report = context.report();
+    let rejected: Vec<(&str, &[String])> = report
+        .rejected
+        .iter()
+        .map(|r: &Rejection| (r.member.as_deref().unwrap(), r.missing_classes.as_slice()))
+        .collect();
+    let missing: Vec<String> = ["java/util/Optional", "test/Missing", "test/Other"]
+        .map(str::to_owned)
+        .to_vec();
+    assert_eq!(rejected, [("find", missing.as_slice())]);
+    assert_eq!(report.missing_classes.len(), 3);
+
+    // Losing a variant to a name collision doesn't reject the member
+    let skipped: Vec<(&str, &str, &str)> = report
+        .skipped
+        .iter()
+        .map(|s: &Skipped| (s.member.as_str(), s.name.as_str(), s.reason.as_str()))
+        .collect();
+    assert_eq!(
+        skipped,
+        [("name", "name_string", "the name is already taken")]
+    );
+    assert!(!report.is_empty());
+}
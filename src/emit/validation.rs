@@ -0,0 +1,55 @@
+use super::{classes::Class, cstring, fields::Field, methods::Method};
+use crate::identifiers::FieldMangling;
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+impl Method<'_> {
+    /// Records this method in `__report` if `__jni_class` lacks it.
+    pub fn validation_check(&self) -> TokenStream {
+        let class: Literal = cstring(self.class.path().as_str());
+        let java_name: Literal = cstring(self.java.name());
+        let descriptor: Literal = cstring(&self.java.descriptor().to_string());
+        let is_static: bool = self.java.is_static();
+        quote!(__report.method(__jni_env, &__jni_class, #class, #java_name, #descriptor, #is_static);)
+    }
+}
+
+impl Field<'_> {
+    /// Records this field in `__report` if `__jni_class` lacks it.
+    ///
+    /// Constants are inlined by the bindings, so they're never checked.
+    pub fn validation_check(&self) -> TokenStream {
+        if !matches!(self.rust_names, Ok(FieldMangling::GetSet(..))) {
+            return TokenStream::new();
+        }
+        let class: Literal = cstring(self.class.path().as_str());
+        let java_name: Literal = cstring(self.java.name());
+        let descriptor: Literal = cstring(&self.java.descriptor().to_string());
+        let is_static: bool = self.java.is_static();
+        quote!(__report.field(__jni_env, &__jni_class, #class, #java_name, #descriptor, #is_static);)
+    }
+}
+
+impl Class {
+    /// Emits `__validate_bindings()`, which runs the `checks` of the bound members, and the checks of this class'
+    /// Java proxy if it `has_proxy` and the caller asks for them.
+    pub fn write_validation(&self, checks: TokenStream, has_proxy: bool) -> TokenStream {
+        let class: Literal = cstring(self.java.path().as_str());
+        let proxy: TokenStream = match has_proxy {
+            true => quote!(if proxies {
+                Self::__validate_proxy(__jni_env, __report);
+            }),
+            false => quote!(let _ = proxies;),
+        };
+        quote!(
+            #[doc(hidden)]
+            pub fn __validate_bindings(__jni_env: ::java_oxide::Env, __report: &mut crate::validation::Report, proxies: bool) {
+                #proxy
+                let ::std::option::Option::Some(__jni_class) = __report.class(__jni_env, #class) else {
+                    return;
+                };
+                #checks
+            }
+        )
+    }
+}
@@ -1,9 +1,15 @@
 // this must go first because of macros.
-use crate::{cli::Cli, config::Config, parser_util::JavaClass, prelude::*};
+use crate::{
+    cli::{Cli, ReportArgs},
+    config::Config,
+    parser_util::JavaClass,
+    prelude::*,
+};
 use std::{
+    collections::HashSet,
     fs::File,
     io::{self, BufReader, Read},
-    path::Path,
+    path::{Path, PathBuf},
 };
 use zip::{ZipArchive, read::ZipFile};
 
@@ -12,8 +18,11 @@ mod config;
 mod emit;
 mod identifiers;
 mod macros;
+mod manifest;
 mod parser_util;
+mod report;
 mod setup;
+mod transitive;
 mod util;
 pub mod prelude {
     #[allow(unused_imports)]
@@ -23,23 +32,69 @@ pub mod prelude {
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
 /// The core function of this library: Generate Rust code to access Java APIs.
-pub fn run(config: impl Into<Config>) {
-    let config: Config = config.into();
+///
+/// Only fails when members were rejected despite `--deny-rejections`.
+pub fn run(config: impl Into<Config>, report_args: &ReportArgs) -> anyhow::Result<()> {
+    let mut config: Config = config.into();
     info!("Output: {}", pretty_path!(config.src.output));
 
     info!("Gathering classes...");
+    // Read ahead of the context binding the classes, as transitive rules skip them and extend its config
+    let externs: HashMap<String, emit::ExternClass> = {
+        let mut context: emit::Context<'_> = emit::Context::new(&config);
+        for extern_ in config.externs.iter() {
+            context.add_extern(extern_).unwrap();
+        }
+        context.externs
+    };
+
+    // Transitive rules need every class before binding any, as the classes they pull in extend the config
+    let transitive: bool = config.rules.iter().any(|rule| rule.reach().is_some());
+    let mut inputs: Vec<(PathBuf, Vec<JavaClass>)> = Vec::new();
+    if transitive {
+        for file in config.src.inputs.iter() {
+            inputs.push((file.clone(), read_file(file).unwrap()));
+        }
+        let classes: Vec<&JavaClass> = inputs.iter().flat_map(|(_, classes)| classes).collect();
+        transitive::include_referenced(&mut config, &classes, &externs);
+    }
+
     let mut context: emit::Context<'_> = emit::Context::new(&config);
-    for file in config.src.inputs.iter() {
-        gather_file(&mut context, file).unwrap();
+    context.externs = externs;
+    if transitive {
+        for (file, classes) in inputs {
+            gather_classes(&mut context, &file, classes).unwrap();
+        }
+    } else {
+        for file in config.src.inputs.iter() {
+            gather_classes(&mut context, file, read_file(file).unwrap()).unwrap();
+        }
     }
 
-    let mut out: Vec<u8> = Vec::with_capacity(4096);
-    context.write(&mut out).unwrap();
-    info!("Writing bindings...");
-    match util::write_generated(&config.src.output, &out[..]) {
-        Ok(_) => {}
-        Err(e) => error!("ERROR WHILE WRITING BINDINGS:\n{}", e),
-    };
+    if config.src.is_crate_output() {
+        let files: Vec<(PathBuf, Vec<u8>)> = context.write_crate(&config.src.output).unwrap();
+        info!("Writing bindings...");
+        match write_crate(&config.src.output, files) {
+            Ok(_) => {}
+            Err(e) => error!("ERROR WHILE WRITING BINDINGS:\n{}", e),
+        };
+    } else {
+        let mut out: Vec<u8> = Vec::with_capacity(4096);
+        context.write(&mut out).unwrap();
+        info!("Writing bindings...");
+        match util::write_generated(&config.src.output, &out[..]) {
+            Ok(_) => {}
+            Err(e) => error!("ERROR WHILE WRITING BINDINGS:\n{}", e),
+        };
+    }
+
+    if let Some(path) = &config.src.manifest {
+        info!("Writing manifest: {}", pretty_path!(path));
+        match context.manifest().write(path) {
+            Ok(_) => {}
+            Err(e) => error!("ERROR WHILE WRITING MANIFEST:\n{}", e),
+        };
+    }
 
     // Generate Java proxy files if proxy_output is specified
     // dbg!(&config.proxy.output);
@@ -49,9 +104,48 @@ pub fn run(config: impl Into<Config>) {
             Err(e) => error!("ERROR WHILE WRITING PROXIES:\n{}", e),
         };
     }
+
+    if report_args.is_requested() {
+        let report: report::Report = context.report();
+        if report_args.report {
+            info!("Rejections: {}", report);
+        }
+        if let Some(path) = &report_args.report_json {
+            info!("Writing report: {}", pretty_path!(path));
+            match report.write(path) {
+                Ok(_) => {}
+                Err(e) => error!("ERROR WHILE WRITING REPORT:\n{}", e),
+            };
+        }
+        if report_args.deny_rejections && !report.is_empty() {
+            anyhow::bail!(
+                "{} members of bound classes were rejected, see `--report`",
+                report.rejected.len()
+            );
+        }
+    }
+    Ok(())
 }
 
-fn gather_file(context: &mut emit::Context, path: &Path) -> Result<(), anyhow::Error> {
+/// Writes the `files` of a bindings crate in `dir`, then removes generated sources left over from previous runs.
+///
+/// A `Cargo.toml` without the marker comment is left as is, so it can be customized.
+fn write_crate(dir: &Path, files: Vec<(PathBuf, Vec<u8>)>) -> io::Result<()> {
+    let mut written: HashSet<PathBuf> = HashSet::new();
+    for (path, contents) in files {
+        if path.ends_with("Cargo.toml") && path.exists() && !util::is_generated(&path)? {
+            info!("Customized: {:?}...", pretty_path!(path));
+            continue;
+        }
+        util::write_generated(&path, &contents[..])?;
+        written.insert(path);
+    }
+    util::remove_stale_generated(&dir.join("src"), &written)?;
+    Ok(())
+}
+
+/// Reads the classes of a `.class` or `.jar` file.
+fn read_file(path: &Path) -> Result<Vec<JavaClass>, anyhow::Error> {
     info!("Reading {:?}...", pretty_path!(path));
 
     let ext: &std::ffi::OsStr = if let Some(ext) = path.extension() {
@@ -66,14 +160,7 @@ fn gather_file(context: &mut emit::Context, path: &Path) -> Result<(), anyhow::E
     match ext.to_string_lossy().to_ascii_lowercase().as_str() {
         "class" => {
             debug!("Reading class directly...");
-            let class: JavaClass = JavaClass::read(std::fs::read(path)?)?;
-            let class_path: String = class.path().as_str().to_string();
-            if !context.add_class(class)? {
-                warn!(
-                    "Classfile ({:?}) will not be bound because it is not included in the config file!",
-                    class_path
-                )
-            }
+            Ok(vec![JavaClass::read(std::fs::read(path)?)?])
         }
         "jar" => {
             let mut jar: ZipArchive<BufReader<File>> =
@@ -86,10 +173,10 @@ fn gather_file(context: &mut emit::Context, path: &Path) -> Result<(), anyhow::E
                 classfiles.push(file.to_owned());
             }
             let num_files: usize = classfiles.len();
-            let mut num_bound: usize = 0;
 
             debug!("Reading {} classes from JAR...", num_files);
 
+            let mut classes: Vec<JavaClass> = Vec::with_capacity(num_files);
             #[allow(clippy::unused_enumerate_index)]
             for (_i, file) in classfiles.iter().enumerate() {
                 let mut file: ZipFile<'_, BufReader<File>> = jar.by_name(file)?;
@@ -102,30 +189,59 @@ fn gather_file(context: &mut emit::Context, path: &Path) -> Result<(), anyhow::E
                 // );
                 let mut buf: Vec<u8> = Vec::new();
                 file.read_to_end(&mut buf)?;
-                let class: JavaClass = JavaClass::read(buf)?;
-                if context.add_class(class)? {
-                    num_bound += 1;
-                }
+                classes.push(JavaClass::read(buf)?);
             }
+            Ok(classes)
+        }
+        unknown => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Input files must have a '.class' or '.jar' extension, not a '.{}' extension",
+                unknown
+            ),
+        ))?,
+    }
+}
 
-            if num_bound == 0 {
+/// Adds the classes read from the input file at `path` to `context`, warning if none of them are bound.
+fn gather_classes(
+    context: &mut emit::Context,
+    path: &Path,
+    classes: Vec<JavaClass>,
+) -> Result<(), anyhow::Error> {
+    let is_jar: bool = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"));
+    if !is_jar {
+        for class in classes {
+            let class_path: String = class.path().as_str().to_string();
+            if !context.add_class(class)? {
                 warn!(
-                    "No classes from the JAR were bound because none of them were included in the config file!"
-                );
-            } else {
-                debug!("{} classes added from JAR", num_bound);
+                    "Classfile ({:?}) will not be bound because it is not included in the config file!",
+                    class_path
+                )
             }
         }
-        unknown => {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "Input files must have a '.class' or '.jar' extension, not a '.{}' extension",
-                    unknown
-                ),
-            ))?;
+        return Ok(());
+    }
+
+    let mut num_bound: usize = 0;
+    // Classes bound by `[[extern]]` crates are included, just not bound again
+    let mut any_extern: bool = false;
+    for class in classes {
+        any_extern |= context.externs.contains_key(class.path().as_str());
+        if context.add_class(class)? {
+            num_bound += 1;
         }
     }
+
+    if num_bound == 0 && !any_extern {
+        warn!(
+            "No classes from the JAR were bound because none of them were included in the config file!"
+        );
+    } else {
+        debug!("{} classes added from JAR", num_bound);
+    }
     Ok(())
 }
 
@@ -140,7 +256,10 @@ pub fn main() {
             } else {
                 config::Config::from_current_directory().unwrap()
             };
-            run(config);
+            if let Err(e) = run(config, &cmd.report) {
+                error!("{}", e);
+                std::process::exit(1);
+            }
         }
     }
 }
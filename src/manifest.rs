@@ -0,0 +1,138 @@
+//! Binding manifests: machine-readable descriptions of the classes bound by a bindings crate, written alongside them
+//! when `sources.manifest` is set, letting other crates depend on it through `[[extern]]` instead of binding its
+//! classes again.
+
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io, path::Path};
+
+/// Everything a bindings crate bound.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Manifest {
+    pub classes: Vec<ManifestClass>,
+}
+
+/// A class bound by a bindings crate.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestClass {
+    /// JNI path of the class, such as "java/lang/String"
+    pub java: String,
+    /// Path of its Rust type from the root of the bindings crate, such as "java::lang::String"
+    pub rust: String,
+    /// JNI path of its superclass, if any
+    #[serde(default)]
+    pub superclass: Option<String>,
+    /// JNI paths of the interfaces it directly implements
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+    /// Its generic `Signature` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Cargo feature of the bindings crate gating it, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    /// The options its methods were bound with, which convert some of their parameter and return types
+    #[serde(default)]
+    pub conversions: ManifestConversions,
+    /// Its Rust proxy, if the config asked for one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ManifestProxy>,
+    /// Its methods and fields allowed by the config, whether they were emitted or not
+    #[serde(default)]
+    pub members: Vec<ManifestMember>,
+}
+
+/// The Rust proxy of a bound class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestProxy {
+    /// Whether the proxy was emitted
+    pub emitted: bool,
+    /// Why the proxy was rejected, if it wasn't emitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected: Option<String>,
+    /// JNI paths of the unbound classes its methods reference, if it was rejected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_classes: Vec<String>,
+}
+
+/// The class options converting the Rust parameter and return types of the methods of a bound class, see their
+/// descriptions in the config.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ManifestConversions {
+    /// Whether `java.lang.String` parameters accept `impl IntoJavaStr`
+    pub rust_strings: bool,
+    /// Whether wrapper class parameters accept `impl IntoBoxed` and returned wrapper classes are unboxed
+    pub boxed_primitives: bool,
+    /// Whether `java.util.Optional*` parameters accept `impl IntoOptional`
+    pub optionals: bool,
+}
+
+/// A method or field of a bound class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestMember {
+    /// Java name of the member, such as "valueOf" or "<init>"
+    pub java: String,
+    /// JNI descriptor of the member, such as "(I)Ljava/lang/String;"
+    pub descriptor: String,
+    /// Its generic `Signature` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Name of the Rust method or constant, or of the getter of a field, if it could be mangled
+    #[serde(default)]
+    pub rust: Option<String>,
+    /// Whether the member is called through the dispatching method `rust`, along with its overloads
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dispatched: bool,
+    /// Name of the Rust setter of a non-final field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rust_setter: Option<String>,
+    pub kind: MemberKind,
+    /// Whether the member was emitted
+    pub emitted: bool,
+    /// Why the member was rejected, if it wasn't emitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected: Option<String>,
+    /// JNI paths of the unbound classes it references, if it was rejected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_classes: Vec<String>,
+    /// Cargo features gating it besides the `feature` of its class
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    /// Its variants and lookup companions which weren't emitted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<ManifestSkipped>,
+}
+
+/// A variant or lookup companion of a member, such as `name_string` or `try_name`, which wasn't emitted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestSkipped {
+    /// Name of the Rust method
+    pub name: String,
+    /// Why it wasn't emitted, such as "the name is already taken"
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemberKind {
+    Constructor,
+    Method,
+    StaticMethod,
+    Field,
+    StaticField,
+    Constant,
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let file: File = File::open(path)?;
+        serde_json::from_reader(io::BufReader::new(file)).map_err(io::Error::from)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file: File = File::create(path)?;
+        serde_json::to_writer_pretty(io::BufWriter::new(file), self).map_err(io::Error::from)
+    }
+}
@@ -95,6 +95,17 @@ impl JavaClass {
         self.get().methods.iter()
     }
 
+    /// The generic `Signature` attribute of this class, if it has one.
+    pub fn signature(&self) -> Option<&str> {
+        self.get()
+            .attributes
+            .iter()
+            .find_map(|attr: &AttributeInfo<'_>| match &attr.data {
+                AttributeData::Signature(signature) => Some(signature.as_ref()),
+                _ => None,
+            })
+    }
+
     pub fn deprecated(&self) -> bool {
         self.get()
             .attributes
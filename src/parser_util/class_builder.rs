@@ -0,0 +1,229 @@
+//! Minimal class files assembled in memory, for tests.
+
+use super::JavaClass;
+use crate::{config::Config, emit::Context};
+
+const ACC_PUBLIC: u16 = 0x0001;
+const ACC_INTERFACE: u16 = 0x0200;
+const ACC_ABSTRACT: u16 = 0x0400;
+
+struct Member {
+    access: u16,
+    name: String,
+    descriptor: String,
+    signature: Option<String>,
+}
+
+/// A public class or interface, declaring public members without code.
+pub struct ClassBuilder {
+    access: u16,
+    path: String,
+    superclass: Option<String>,
+    interfaces: Vec<String>,
+    signature: Option<String>,
+    fields: Vec<Member>,
+    methods: Vec<Member>,
+}
+
+impl ClassBuilder {
+    pub fn class(path: &str) -> Self {
+        Self {
+            access: ACC_PUBLIC,
+            path: path.to_owned(),
+            superclass: Some("java/lang/Object".to_owned()).filter(|_| path != "java/lang/Object"),
+            interfaces: Vec::new(),
+            signature: None,
+            fields: Vec::new(),
+            methods: Vec::new(),
+        }
+    }
+
+    pub fn interface(path: &str) -> Self {
+        Self {
+            access: ACC_PUBLIC | ACC_INTERFACE | ACC_ABSTRACT,
+            ..Self::class(path)
+        }
+    }
+
+    pub fn extends(mut self, superclass: &str) -> Self {
+        self.superclass = Some(superclass.to_owned());
+        self
+    }
+
+    pub fn implements(mut self, interface: &str) -> Self {
+        self.interfaces.push(interface.to_owned());
+        self
+    }
+
+    /// Sets the generic `Signature` of the class.
+    pub fn signature(mut self, signature: &str) -> Self {
+        self.signature = Some(signature.to_owned());
+        self
+    }
+
+    /// Adds an abstract method.
+    pub fn method(self, name: &str, descriptor: &str) -> Self {
+        self.method_with(ACC_PUBLIC | ACC_ABSTRACT, name, descriptor, None)
+    }
+
+    /// Adds a non-abstract method, a default method in interfaces.
+    pub fn concrete_method(self, name: &str, descriptor: &str) -> Self {
+        self.method_with(ACC_PUBLIC, name, descriptor, None)
+    }
+
+    /// Adds an abstract method with a generic `Signature`.
+    pub fn generic_method(self, name: &str, descriptor: &str, signature: &str) -> Self {
+        self.method_with(ACC_PUBLIC | ACC_ABSTRACT, name, descriptor, Some(signature))
+    }
+
+    /// Adds a field with a generic `Signature`.
+    pub fn generic_field(mut self, name: &str, descriptor: &str, signature: &str) -> Self {
+        self.fields.push(Member {
+            access: ACC_PUBLIC,
+            name: name.to_owned(),
+            descriptor: descriptor.to_owned(),
+            signature: Some(signature.to_owned()),
+        });
+        self
+    }
+
+    fn method_with(
+        mut self,
+        access: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+    ) -> Self {
+        self.methods.push(Member {
+            access,
+            name: name.to_owned(),
+            descriptor: descriptor.to_owned(),
+            signature: signature.map(str::to_owned),
+        });
+        self
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        let mut pool: ConstantPool = ConstantPool::default();
+        let mut body: Vec<u8> = Vec::new();
+
+        push_u16(&mut body, self.access);
+        push_u16(&mut body, pool.class(&self.path));
+        let superclass: u16 = match &self.superclass {
+            Some(superclass) => pool.class(superclass),
+            None => 0,
+        };
+        push_u16(&mut body, superclass);
+        push_u16(&mut body, self.interfaces.len() as u16);
+        for interface in &self.interfaces {
+            push_u16(&mut body, pool.class(interface));
+        }
+        for members in [&self.fields, &self.methods] {
+            push_u16(&mut body, members.len() as u16);
+            for member in members {
+                push_u16(&mut body, member.access);
+                push_u16(&mut body, pool.utf8(&member.name));
+                push_u16(&mut body, pool.utf8(&member.descriptor));
+                push_signature(&mut body, &mut pool, member.signature.as_deref());
+            }
+        }
+        push_signature(&mut body, &mut pool, self.signature.as_deref());
+
+        let mut bytes: Vec<u8> = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 52];
+        push_u16(&mut bytes, pool.count + 1);
+        bytes.extend(pool.bytes);
+        bytes.extend(body);
+        bytes
+    }
+
+    pub fn build(&self) -> JavaClass {
+        JavaClass::read(self.bytes()).unwrap()
+    }
+}
+
+/// A config and the classes it binds, the setup shared by tests of the generated bindings.
+pub struct Fixture {
+    pub config: Config,
+    classes: Vec<ClassBuilder>,
+}
+
+impl Fixture {
+    /// Reads `classes` as if from `test.jar`, writing to `out.rs`. `config` continues the `[sources]` table, so it can
+    /// set other source options before its `[[include]]` rules.
+    pub fn new(config: &str, classes: impl IntoIterator<Item = ClassBuilder>) -> Self {
+        Self {
+            config: toml::from_str(&format!(
+                "[sources]\ninputs = [\"test.jar\"]\noutput = \"out.rs\"\n{config}"
+            ))
+            .unwrap(),
+            classes: classes.into_iter().collect(),
+        }
+    }
+
+    pub fn classes(&self) -> Vec<JavaClass> {
+        self.classes.iter().map(ClassBuilder::build).collect()
+    }
+
+    /// A context the classes were added to.
+    pub fn context(&self) -> Context<'_> {
+        let mut context: Context = Context::new(&self.config);
+        for class in self.classes() {
+            context.add_class(class).unwrap();
+        }
+        context
+    }
+}
+
+#[derive(Default)]
+struct ConstantPool {
+    entries: Vec<(u8, String, u16)>,
+    count: u16,
+    bytes: Vec<u8>,
+}
+
+impl ConstantPool {
+    fn utf8(&mut self, value: &str) -> u16 {
+        self.entry(1, value, |pool: &mut Self| {
+            push_u16(&mut pool.bytes, value.len() as u16);
+            pool.bytes.extend(value.as_bytes());
+        })
+    }
+
+    fn class(&mut self, path: &str) -> u16 {
+        let name: u16 = self.utf8(path);
+        self.entry(7, path, |pool: &mut Self| push_u16(&mut pool.bytes, name))
+    }
+
+    fn entry(&mut self, tag: u8, value: &str, write: impl FnOnce(&mut Self)) -> u16 {
+        if let Some((_, _, idx)) = self
+            .entries
+            .iter()
+            .find(|(t, v, _)| *t == tag && v == value)
+        {
+            return *idx;
+        }
+        self.count += 1;
+        let idx: u16 = self.count;
+        self.entries.push((tag, value.to_owned(), idx));
+        self.bytes.push(tag);
+        write(self);
+        idx
+    }
+}
+
+fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend(value.to_be_bytes());
+}
+
+/// Writes the attributes of a member or class: a `Signature` if there's one.
+fn push_signature(bytes: &mut Vec<u8>, pool: &mut ConstantPool, signature: Option<&str>) {
+    match signature {
+        Some(signature) => {
+            push_u16(bytes, 1);
+            push_u16(bytes, pool.utf8("Signature"));
+            bytes.extend(2u32.to_be_bytes());
+            push_u16(bytes, pool.utf8(signature));
+        }
+        None => push_u16(bytes, 0),
+    }
+}
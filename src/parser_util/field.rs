@@ -92,6 +92,17 @@ impl<'a> JavaField<'a> {
             .any(|attr: &AttributeInfo<'a>| matches!(attr.data, AttributeData::Deprecated))
     }
 
+    /// The generic `Signature` attribute of this field, if it has one.
+    pub fn signature(&self) -> Option<&'a str> {
+        self.java
+            .attributes
+            .iter()
+            .find_map(|attr: &'a AttributeInfo<'a>| match &attr.data {
+                AttributeData::Signature(signature) => Some(signature.as_ref()),
+                _ => None,
+            })
+    }
+
     pub fn descriptor<'s>(&'s self) -> &'a FieldDescriptor<'a> {
         &self.java.descriptor
     }
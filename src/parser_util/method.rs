@@ -58,7 +58,6 @@ impl<'a> JavaMethod<'a> {
     pub fn is_native(&self) -> bool {
         self.access_flags.contains(MethodAccessFlags::NATIVE)
     }
-    #[allow(unused)]
     pub fn is_abstract(&self) -> bool {
         self.access_flags.contains(MethodAccessFlags::ABSTRACT)
     }
@@ -97,6 +96,17 @@ impl<'a> JavaMethod<'a> {
             .any(|attr: &AttributeInfo<'a>| matches!(attr.data, AttributeData::Deprecated))
     }
 
+    /// The generic `Signature` attribute of this method, if it has one.
+    pub fn signature(&self) -> Option<&'a str> {
+        self.java
+            .attributes
+            .iter()
+            .find_map(|attr: &'a AttributeInfo<'a>| match &attr.data {
+                AttributeData::Signature(signature) => Some(signature.as_ref()),
+                _ => None,
+            })
+    }
+
     pub fn descriptor<'s>(&'s self) -> &'a MethodDescriptor<'a> {
         &self.java.descriptor
     }
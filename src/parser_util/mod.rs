@@ -1,7 +1,10 @@
 mod class;
+#[cfg(test)]
+pub mod class_builder;
 mod field;
 mod id;
 mod method;
+pub mod signature;
 
 pub use class::JavaClass;
 pub use field::JavaField;
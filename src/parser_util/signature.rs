@@ -0,0 +1,467 @@
+//! Parsing of generic `Signature` attributes, see
+//! <https://docs.oracle.com/javase/specs/jvms/se21/html/jvms-4.html#jvms-4.7.9.1>.
+
+use anyhow::{anyhow, bail};
+use cafebabe::descriptors::{
+    ClassName, FieldDescriptor, FieldType, MethodDescriptor, ReturnDescriptor,
+};
+use std::{borrow::Cow, collections::BTreeSet};
+
+/// A generic class signature: `<T:Ljava/lang/Object;>Ljava/lang/Object;Ljava/lang/Iterable<TT;>;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassSignature {
+    /// Names of the declared type parameters, in order.
+    pub type_params: Vec<String>,
+    pub superclass: ClassTypeSignature,
+    pub interfaces: Vec<ClassTypeSignature>,
+}
+
+/// A parameterized class type: `Ljava/util/Map<TK;Ljava/lang/String;>;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassTypeSignature {
+    /// JNI path of the class, with `$` separating inner classes.
+    pub path: String,
+    /// Type arguments of the innermost class.
+    pub type_args: Vec<TypeArgument>,
+}
+
+/// A generic method signature: `<T:Ljava/lang/Object;>(TT;I)Ljava/util/Optional<TT;>;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodTypeSignature {
+    pub params: Vec<TypeSignature>,
+    /// `None` for `void`.
+    pub ret: Option<TypeSignature>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeSignature {
+    /// A primitive, as its descriptor character.
+    Base(char),
+    Class(ClassTypeSignature),
+    TypeVariable(String),
+    Array(Box<TypeSignature>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeArgument {
+    /// `*`
+    Any,
+    /// `T`
+    Exact(TypeSignature),
+    /// `? extends T`
+    Extends(TypeSignature),
+    /// `? super T`
+    Super(TypeSignature),
+}
+
+impl ClassTypeSignature {
+    /// Adds the JNI paths of this class and of the classes in its type arguments to `out`.
+    pub fn collect_classes(&self, out: &mut BTreeSet<String>) {
+        out.insert(self.path.clone());
+        for arg in &self.type_args {
+            match arg {
+                TypeArgument::Any => {}
+                TypeArgument::Exact(ty) | TypeArgument::Extends(ty) | TypeArgument::Super(ty) => {
+                    ty.collect_classes(out)
+                }
+            }
+        }
+    }
+}
+
+impl TypeSignature {
+    /// Adds the JNI paths of the classes this type references to `out`.
+    pub fn collect_classes(&self, out: &mut BTreeSet<String>) {
+        match self {
+            Self::Base(_) | Self::TypeVariable(_) => {}
+            Self::Class(class) => class.collect_classes(out),
+            Self::Array(element) => element.collect_classes(out),
+        }
+    }
+}
+
+#[test]
+fn parse_class_signature_test() {
+    let object = || ClassTypeSignature {
+        path: "java/lang/Object".to_owned(),
+        type_args: Vec::new(),
+    };
+
+    assert_eq!(
+        parse_class_signature("Ljava/lang/Object;Ljava/lang/Iterable<Ltest/Player;>;").unwrap(),
+        ClassSignature {
+            type_params: Vec::new(),
+            superclass: object(),
+            interfaces: vec![ClassTypeSignature {
+                path: "java/lang/Iterable".to_owned(),
+                type_args: vec![TypeArgument::Exact(TypeSignature::Class(
+                    ClassTypeSignature {
+                        path: "test/Player".to_owned(),
+                        type_args: Vec::new(),
+                    }
+                ))],
+            }],
+        }
+    );
+
+    assert_eq!(
+        parse_class_signature(
+            "<K:Ljava/lang/Object;V::Ljava/lang/Comparable<-TV;>;>Ljava/util/AbstractMap<TK;[TV;>;Ljava/util/Map<*+TK;>;"
+        )
+        .unwrap(),
+        ClassSignature {
+            type_params: vec!["K".to_owned(), "V".to_owned()],
+            superclass: ClassTypeSignature {
+                path: "java/util/AbstractMap".to_owned(),
+                type_args: vec![
+                    TypeArgument::Exact(TypeSignature::TypeVariable("K".to_owned())),
+                    TypeArgument::Exact(TypeSignature::Array(Box::new(
+                        TypeSignature::TypeVariable("V".to_owned())
+                    ))),
+                ],
+            },
+            interfaces: vec![ClassTypeSignature {
+                path: "java/util/Map".to_owned(),
+                type_args: vec![
+                    TypeArgument::Any,
+                    TypeArgument::Extends(TypeSignature::TypeVariable("K".to_owned())),
+                ],
+            }],
+        }
+    );
+
+    assert_eq!(
+        parse_class_signature("Ljava/util/Map$Entry<TK;TV;>.Inner<[I>;")
+            .unwrap()
+            .superclass,
+        ClassTypeSignature {
+            path: "java/util/Map$Entry$Inner".to_owned(),
+            type_args: vec![TypeArgument::Exact(TypeSignature::Array(Box::new(
+                TypeSignature::Base('I')
+            )))],
+        }
+    );
+
+    assert!(parse_class_signature("Ljava/lang/Object").is_err());
+    assert!(parse_class_signature("<T:>Ljava/lang/Object;").is_ok());
+}
+
+#[test]
+fn parse_method_signature_test() {
+    let class = |path: &str, type_args: Vec<TypeArgument>| {
+        TypeSignature::Class(ClassTypeSignature {
+            path: path.to_owned(),
+            type_args,
+        })
+    };
+
+    assert_eq!(
+        parse_method_signature(
+            "<T:Ljava/lang/Object;>(TT;[I)Ljava/util/Optional<+Ltest/World;>;^Ljava/io/IOException;"
+        )
+        .unwrap(),
+        MethodTypeSignature {
+            params: vec![
+                TypeSignature::TypeVariable("T".to_owned()),
+                TypeSignature::Array(Box::new(TypeSignature::Base('I'))),
+            ],
+            ret: Some(class(
+                "java/util/Optional",
+                vec![TypeArgument::Extends(class("test/World", Vec::new()))]
+            )),
+        }
+    );
+
+    assert_eq!(
+        parse_method_signature("(Ljava/util/Optional<Ljava/lang/String;>;)V").unwrap(),
+        MethodTypeSignature {
+            params: vec![class(
+                "java/util/Optional",
+                vec![TypeArgument::Exact(class("java/lang/String", Vec::new()))]
+            )],
+            ret: None,
+        }
+    );
+
+    assert!(parse_method_signature("(I").is_err());
+}
+
+#[test]
+fn parse_field_signature_test() {
+    assert_eq!(
+        parse_field_signature("Ljava/util/List<Ltest/Player;>;").unwrap(),
+        TypeSignature::Class(ClassTypeSignature {
+            path: "java/util/List".to_owned(),
+            type_args: vec![TypeArgument::Exact(TypeSignature::Class(
+                ClassTypeSignature {
+                    path: "test/Player".to_owned(),
+                    type_args: Vec::new(),
+                }
+            ))],
+        })
+    );
+    assert_eq!(
+        parse_field_signature("TT;").unwrap(),
+        TypeSignature::TypeVariable("T".to_owned())
+    );
+    assert!(parse_field_signature("Ljava/util/List;I").is_err());
+}
+
+#[test]
+fn parse_method_descriptor_test() {
+    let descriptor: MethodDescriptor<'_> =
+        parse_method_descriptor("(I[[Ljava/lang/String;)Ljava/util/Map$Entry;").unwrap();
+    assert_eq!(
+        descriptor.to_string(),
+        "(I[[Ljava/lang/String;)Ljava/util/Map$Entry;"
+    );
+    assert_eq!(descriptor.parameters[1].dimensions, 2);
+    assert_eq!(
+        parse_method_descriptor("()V").unwrap().return_type,
+        ReturnDescriptor::Void
+    );
+
+    assert!(parse_method_descriptor("(TT;)V").is_err());
+    assert!(parse_method_descriptor("()Ljava/util/List<Ljava/lang/String;>;").is_err());
+    assert!(parse_method_descriptor("<T:Ljava/lang/Object;>()V").is_err());
+}
+
+/// Parses the `Signature` attribute of a class.
+pub fn parse_class_signature(signature: &str) -> anyhow::Result<ClassSignature> {
+    let mut parser: Parser<'_> = Parser {
+        rest: signature,
+        signature,
+    };
+
+    let type_params: Vec<String> = parser.type_parameters()?;
+
+    let superclass: ClassTypeSignature = parser.class_type()?;
+    let mut interfaces: Vec<ClassTypeSignature> = Vec::new();
+    while !parser.rest.is_empty() {
+        interfaces.push(parser.class_type()?);
+    }
+
+    Ok(ClassSignature {
+        type_params,
+        superclass,
+        interfaces,
+    })
+}
+
+/// Parses the `Signature` attribute of a method.
+pub fn parse_method_signature(signature: &str) -> anyhow::Result<MethodTypeSignature> {
+    let mut parser: Parser<'_> = Parser {
+        rest: signature,
+        signature,
+    };
+
+    parser.type_parameters()?;
+
+    parser.expect('(')?;
+    let mut params: Vec<TypeSignature> = Vec::new();
+    while !parser.eat(')') {
+        params.push(parser.java_type()?);
+    }
+    let ret: Option<TypeSignature> = match parser.eat('V') {
+        true => None,
+        false => Some(parser.java_type()?),
+    };
+
+    // Thrown exceptions aren't needed
+    if !parser.rest.is_empty() && !parser.rest.starts_with('^') {
+        bail!("unexpected {:?} in {:?}", parser.rest, signature);
+    }
+
+    Ok(MethodTypeSignature { params, ret })
+}
+
+/// Parses the `Signature` attribute of a field.
+pub fn parse_field_signature(signature: &str) -> anyhow::Result<TypeSignature> {
+    let mut parser: Parser<'_> = Parser {
+        rest: signature,
+        signature,
+    };
+
+    let ty: TypeSignature = parser.reference_type()?;
+    if !parser.rest.is_empty() {
+        bail!("unexpected {:?} in {:?}", parser.rest, signature);
+    }
+    Ok(ty)
+}
+
+/// Parses a JNI method descriptor, such as one recorded in a binding manifest.
+pub fn parse_method_descriptor(descriptor: &str) -> anyhow::Result<MethodDescriptor<'static>> {
+    // Descriptors are signatures without type parameters, type arguments or type variables
+    if !descriptor.starts_with('(') {
+        bail!("expected '(' at the start of {:?}", descriptor);
+    }
+    let signature: MethodTypeSignature = parse_method_signature(descriptor)?;
+    let parameters: Vec<FieldDescriptor<'static>> = signature
+        .params
+        .iter()
+        .map(|ty: &TypeSignature| field_descriptor(ty, descriptor))
+        .collect::<anyhow::Result<_>>()?;
+    let return_type: ReturnDescriptor<'static> = match &signature.ret {
+        Some(ty) => ReturnDescriptor::Return(field_descriptor(ty, descriptor)?),
+        None => ReturnDescriptor::Void,
+    };
+    Ok(MethodDescriptor {
+        parameters,
+        return_type,
+    })
+}
+
+/// The descriptor of `ty`, a type of the method `descriptor`.
+fn field_descriptor(
+    ty: &TypeSignature,
+    descriptor: &str,
+) -> anyhow::Result<FieldDescriptor<'static>> {
+    let field_type: FieldType<'static> = match ty {
+        TypeSignature::Base('B') => FieldType::Byte,
+        TypeSignature::Base('C') => FieldType::Char,
+        TypeSignature::Base('D') => FieldType::Double,
+        TypeSignature::Base('F') => FieldType::Float,
+        TypeSignature::Base('I') => FieldType::Integer,
+        TypeSignature::Base('J') => FieldType::Long,
+        TypeSignature::Base('S') => FieldType::Short,
+        TypeSignature::Base('Z') => FieldType::Boolean,
+        TypeSignature::Class(class) if class.type_args.is_empty() => {
+            FieldType::Object(ClassName::try_from(Cow::Owned(class.path.clone()))?)
+        }
+        TypeSignature::Array(element) => {
+            let mut element: FieldDescriptor<'static> = field_descriptor(element, descriptor)?;
+            element.dimensions += 1;
+            return Ok(element);
+        }
+        _ => bail!("unexpected generic type in the descriptor {:?}", descriptor),
+    };
+    Ok(FieldDescriptor {
+        dimensions: 0,
+        field_type,
+    })
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+    signature: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, c: char) -> bool {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> anyhow::Result<()> {
+        if !self.eat(c) {
+            bail!("expected {c:?} at {:?} in {:?}", self.rest, self.signature);
+        }
+        Ok(())
+    }
+
+    fn next(&mut self) -> anyhow::Result<char> {
+        let mut chars: std::str::Chars<'a> = self.rest.chars();
+        let c: char = chars
+            .next()
+            .ok_or_else(|| anyhow!("unexpected end of {:?}", self.signature))?;
+        self.rest = chars.as_str();
+        Ok(c)
+    }
+
+    /// Consumes an identifier up to (not including) any of `terminators`.
+    fn identifier(&mut self, terminators: &[char]) -> anyhow::Result<&'a str> {
+        let end: usize = self
+            .rest
+            .find(|c: char| terminators.contains(&c))
+            .ok_or_else(|| anyhow!("unterminated identifier in {:?}", self.signature))?;
+        let (identifier, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok(identifier)
+    }
+
+    /// Consumes optional type parameters, returning their names.
+    fn type_parameters(&mut self) -> anyhow::Result<Vec<String>> {
+        let mut type_params: Vec<String> = Vec::new();
+        if self.eat('<') {
+            while !self.eat('>') {
+                type_params.push(self.identifier(&[':'])?.to_owned());
+                // The class bound is empty for interface-only bounds (`T::Ljava/lang/Comparable;`)
+                self.expect(':')?;
+                if self.rest.starts_with(['L', 'T', '[']) {
+                    self.reference_type()?;
+                }
+                while self.eat(':') {
+                    self.reference_type()?;
+                }
+            }
+        }
+        Ok(type_params)
+    }
+
+    fn reference_type(&mut self) -> anyhow::Result<TypeSignature> {
+        match self.rest.chars().next() {
+            Some('L') => Ok(TypeSignature::Class(self.class_type()?)),
+            Some('T') => {
+                self.expect('T')?;
+                let name: &str = self.identifier(&[';'])?;
+                self.expect(';')?;
+                Ok(TypeSignature::TypeVariable(name.to_owned()))
+            }
+            Some('[') => {
+                self.expect('[')?;
+                Ok(TypeSignature::Array(Box::new(self.java_type()?)))
+            }
+            _ => bail!(
+                "expected a reference type at {:?} in {:?}",
+                self.rest,
+                self.signature
+            ),
+        }
+    }
+
+    fn java_type(&mut self) -> anyhow::Result<TypeSignature> {
+        match self.rest.chars().next() {
+            Some(c @ ('B' | 'C' | 'D' | 'F' | 'I' | 'J' | 'S' | 'Z')) => {
+                self.next()?;
+                Ok(TypeSignature::Base(c))
+            }
+            _ => self.reference_type(),
+        }
+    }
+
+    fn class_type(&mut self) -> anyhow::Result<ClassTypeSignature> {
+        self.expect('L')?;
+        let mut path: String = self.identifier(&['<', '.', ';'])?.to_owned();
+        let mut type_args: Vec<TypeArgument> = self.type_arguments()?;
+        while self.eat('.') {
+            path.push('$');
+            path.push_str(self.identifier(&['<', '.', ';'])?);
+            type_args = self.type_arguments()?;
+        }
+        self.expect(';')?;
+        Ok(ClassTypeSignature { path, type_args })
+    }
+
+    fn type_arguments(&mut self) -> anyhow::Result<Vec<TypeArgument>> {
+        let mut type_args: Vec<TypeArgument> = Vec::new();
+        if self.eat('<') {
+            while !self.eat('>') {
+                type_args.push(if self.eat('*') {
+                    TypeArgument::Any
+                } else if self.eat('+') {
+                    TypeArgument::Extends(self.reference_type()?)
+                } else if self.eat('-') {
+                    TypeArgument::Super(self.reference_type()?)
+                } else {
+                    TypeArgument::Exact(self.reference_type()?)
+                });
+            }
+        }
+        Ok(type_args)
+    }
+}
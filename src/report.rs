@@ -0,0 +1,115 @@
+//! Rejection reports: what the bound classes lost, and which missing classes are to blame, so the config can be
+//! completed without grepping the trace output.
+
+use serde::Serialize;
+use std::{fmt, fs::File, io, path::Path};
+
+/// Members of bound classes which couldn't be emitted, and the variants of members which were left out.
+///
+/// Rejections the generator always makes, such as enum `$VALUES` fields, are left out.
+#[derive(Serialize, Debug, Default)]
+pub struct Report {
+    /// Classes referenced by rejected members but not bound, most blocking first
+    pub missing_classes: Vec<MissingClass>,
+    pub rejected: Vec<Rejection>,
+    /// Variants and lookup companions which weren't emitted although their member was, such as those whose name was
+    /// taken
+    pub skipped: Vec<Skipped>,
+}
+
+/// A class which isn't bound, neither by this run nor by an `[[extern]]` crate.
+#[derive(Serialize, Debug)]
+pub struct MissingClass {
+    /// JNI path of the class, such as "java/util/stream/Stream"
+    pub java: String,
+    /// How many rejections it takes part in
+    pub blocks: usize,
+}
+
+/// A member, or the proxy, of a bound class which wasn't emitted.
+#[derive(Serialize, Debug)]
+pub struct Rejection {
+    /// JNI path of the class
+    pub class: String,
+    /// Java name of the member, or `None` for the proxy of the class
+    pub member: Option<String>,
+    /// JNI descriptor of the member
+    pub descriptor: Option<String>,
+    pub reasons: Vec<String>,
+    /// JNI paths of the missing classes it references
+    pub missing_classes: Vec<String>,
+}
+
+/// A variant or lookup companion of a member of a bound class, such as `name_string` or `try_name`, which wasn't
+/// emitted.
+#[derive(Serialize, Debug)]
+pub struct Skipped {
+    /// JNI path of the class
+    pub class: String,
+    /// Java name of the member
+    pub member: String,
+    /// JNI descriptor of the member
+    pub descriptor: String,
+    /// Name of the Rust method
+    pub name: String,
+    pub reason: String,
+}
+
+impl Report {
+    /// Whether no member was rejected, skipped variants aside.
+    pub fn is_empty(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file: File = File::create(path)?;
+        serde_json::to_writer_pretty(io::BufWriter::new(file), self).map_err(io::Error::from)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} rejections, {} skipped variants, {} missing classes",
+            self.rejected.len(),
+            self.skipped.len(),
+            self.missing_classes.len()
+        )?;
+        if !self.missing_classes.is_empty() {
+            write!(f, "\nMissing classes:")?;
+            let width: usize = self.missing_classes[0].blocks.to_string().len();
+            for missing in &self.missing_classes {
+                write!(f, "\n    {:>width$}  {}", missing.blocks, missing.java)?;
+            }
+        }
+        if !self.rejected.is_empty() {
+            write!(f, "\nRejected:")?;
+            for rejection in &self.rejected {
+                match (&rejection.member, &rejection.descriptor) {
+                    (Some(member), Some(descriptor)) => {
+                        write!(f, "\n    {}.{}{}", rejection.class, member, descriptor)?
+                    }
+                    _ => write!(f, "\n    {} (proxy)", rejection.class)?,
+                }
+                for reason in &rejection.reasons {
+                    write!(f, "\n        {reason}")?;
+                }
+            }
+        }
+        if !self.skipped.is_empty() {
+            write!(f, "\nSkipped:")?;
+            for skipped in &self.skipped {
+                write!(
+                    f,
+                    "\n    {}.{}{}\n        {}: {}",
+                    skipped.class, skipped.member, skipped.descriptor, skipped.name, skipped.reason
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
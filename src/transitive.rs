@@ -0,0 +1,291 @@
+//! Transitive includes: binding the classes referenced by bound classes, instead of listing each of them in the config
+//! after it shows up in a rejection report.
+
+use crate::{
+    config::{Config, IncludeConfig},
+    emit::ExternClass,
+    parser_util::{
+        JavaClass, JavaField, JavaMethod,
+        signature::{
+            ClassSignature, MethodTypeSignature, parse_class_signature, parse_field_signature,
+            parse_method_signature,
+        },
+    },
+    prelude::*,
+};
+use cafebabe::descriptors::{FieldDescriptor, FieldType, ReturnDescriptor};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// A class the traversal went through.
+#[derive(Clone, Copy)]
+struct Visit {
+    /// How many more references away classes may be bound
+    reach: u32,
+    /// Index of the transitive rule it was reached from
+    rule: usize,
+}
+
+/// Appends an include rule to `config` for every class of `classes` which is referenced, directly or not, by a class
+/// matched by a transitive rule, within that rule's depth.
+///
+/// Classes of `externs`, bound by `[[extern]]` crates, or explicitly excluded, and non-public classes unless the rule
+/// binds private classes, are neither bound nor traversed.
+pub fn include_referenced(
+    config: &mut Config,
+    classes: &[&JavaClass],
+    externs: &HashMap<String, ExternClass>,
+) {
+    let inputs: HashMap<&str, &JavaClass> = classes
+        .iter()
+        .map(|class: &&JavaClass| (class.path().as_str(), *class))
+        .collect();
+
+    let mut visits: HashMap<&str, Visit> = HashMap::new();
+    let mut queue: VecDeque<&str> = VecDeque::new();
+    let mut paths: Vec<&str> = inputs.keys().copied().collect();
+    paths.sort_unstable();
+    for path in paths {
+        if !config.resolve_class(path).bind || externs.contains_key(path) {
+            continue;
+        }
+        let seed: Option<(usize, u32)> = config
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.matches_class(path))
+            .filter_map(|(idx, rule)| Some((idx, rule.reach()?)))
+            .max_by_key(|(_, reach)| *reach);
+        if let Some((rule, reach)) = seed {
+            visits.insert(path, Visit { reach, rule });
+            queue.push_back(path);
+        }
+    }
+
+    // JNI path of each pulled in class, and the rule and reference it was pulled in by
+    let mut pulled: BTreeMap<&str, (usize, String)> = BTreeMap::new();
+    while let Some(path) = queue.pop_front() {
+        let Visit { reach, rule } = visits[path];
+        if reach == 0 {
+            continue;
+        }
+        let class: &JavaClass = inputs[path];
+        let rule_config: &IncludeConfig = &config.rules[rule];
+        let private_methods: bool = match pulled.contains_key(path) {
+            true => rule_config.bind_private_methods.unwrap_or_default(),
+            false => config.resolve_class(path).bind_private_methods,
+        };
+        let private_fields: bool = match pulled.contains_key(path) {
+            true => rule_config.bind_private_fields.unwrap_or_default(),
+            false => config.resolve_class(path).bind_private_fields,
+        };
+
+        for (referenced, via) in references(class, private_methods, private_fields) {
+            let Some((&referenced, referenced_class)) = inputs.get_key_value(referenced.as_str())
+            else {
+                continue;
+            };
+            if externs.contains_key(referenced) || is_excluded(config, referenced) {
+                continue;
+            }
+            let bound: bool = config.resolve_class(referenced).bind;
+            if !bound
+                && !referenced_class.is_public()
+                && !rule_config.bind_private_classes.unwrap_or_default()
+            {
+                continue;
+            }
+            let reach: u32 = match reach {
+                u32::MAX => u32::MAX,
+                reach => reach - 1,
+            };
+            if visits
+                .get(referenced)
+                .is_some_and(|visit: &Visit| visit.reach >= reach)
+            {
+                continue;
+            }
+            if !bound && !pulled.contains_key(referenced) {
+                info!("Including {:?}, referenced by {}", referenced, via);
+                pulled.insert(referenced, (rule, via));
+            }
+            visits.insert(referenced, Visit { reach, rule });
+            queue.push_back(referenced);
+        }
+    }
+
+    if !pulled.is_empty() {
+        info!("{} classes included transitively", pulled.len());
+    }
+    let rules: Vec<IncludeConfig> = pulled
+        .into_iter()
+        .map(|(path, (rule, _))| IncludeConfig {
+            matches: vec![glob::Pattern::escape(path)],
+            bind: Some(true),
+            proxy: None,
+            transitive: None,
+            depth: None,
+            ..config.rules[rule].clone()
+        })
+        .collect();
+    config.rules.extend(rules);
+}
+
+/// Whether a rule matching `class` sets `bind = false`, which transitive includes don't override.
+fn is_excluded(config: &Config, class: &str) -> bool {
+    config
+        .rules
+        .iter()
+        .any(|rule: &IncludeConfig| rule.bind == Some(false) && rule.matches_class(class))
+}
+
+/// Classes referenced by the superclass, interfaces and bound members of `class`, through their descriptors and
+/// generic signatures, with a description of the reference.
+fn references(
+    class: &JavaClass,
+    private_methods: bool,
+    private_fields: bool,
+) -> Vec<(String, String)> {
+    let path: &str = class.path().as_str();
+    let mut res: Vec<(String, String)> = Vec::new();
+
+    if let Some(superclass) = class.super_path() {
+        res.push((
+            superclass.as_str().to_owned(),
+            format!("{path} (superclass)"),
+        ));
+    }
+    for interface in class.interfaces() {
+        res.push((interface.to_string(), format!("{path} (interface)")));
+    }
+    // Type arguments only show up in signatures, such as the element of `Iterable<Foo>`
+    if let Some(signature) = class.signature()
+        && let Ok(signature) = parse_class_signature(signature)
+    {
+        let ClassSignature {
+            superclass,
+            interfaces,
+            ..
+        } = signature;
+        let mut classes: BTreeSet<String> = BTreeSet::new();
+        for ty in std::iter::once(&superclass).chain(&interfaces) {
+            ty.collect_classes(&mut classes);
+        }
+        res.extend(
+            classes
+                .into_iter()
+                .map(|referenced: String| (referenced, format!("{path} (signature)"))),
+        );
+    }
+    for method in class.methods().map(JavaMethod::from) {
+        if !(method.is_public() || private_methods) || method.is_bridge() || method.is_static_init()
+        {
+            continue;
+        }
+        let descriptor = method.descriptor();
+        let via: String = format!("{path}.{}{}", method.name(), descriptor);
+        let returned: Option<&FieldDescriptor> = match &descriptor.return_type {
+            ReturnDescriptor::Return(desc) => Some(desc),
+            ReturnDescriptor::Void => None,
+        };
+        for desc in descriptor.parameters.iter().chain(returned) {
+            if let FieldType::Object(class_name) = &desc.field_type {
+                res.push((class_name.to_string(), via.clone()));
+            }
+        }
+        if let Some(signature) = method.signature()
+            && let Ok(MethodTypeSignature { params, ret }) = parse_method_signature(signature)
+        {
+            let mut classes: BTreeSet<String> = BTreeSet::new();
+            for ty in params.iter().chain(&ret) {
+                ty.collect_classes(&mut classes);
+            }
+            res.extend(
+                classes
+                    .into_iter()
+                    .map(|referenced: String| (referenced, via.clone())),
+            );
+        }
+    }
+    for field in class.fields().map(JavaField::from) {
+        if !(field.is_public() || private_fields) {
+            continue;
+        }
+        let via: String = format!("{path}.{}", field.name());
+        if let FieldType::Object(class_name) = &field.descriptor().field_type {
+            res.push((class_name.to_string(), via.clone()));
+        }
+        if let Some(signature) = field.signature()
+            && let Ok(ty) = parse_field_signature(signature)
+        {
+            let mut classes: BTreeSet<String> = BTreeSet::new();
+            ty.collect_classes(&mut classes);
+            res.extend(
+                classes
+                    .into_iter()
+                    .map(|referenced: String| (referenced, via.clone())),
+            );
+        }
+    }
+    res
+}
+
+#[test]
+fn include_referenced_test() {
+    use crate::parser_util::class_builder::{ClassBuilder, Fixture};
+
+    let fixture: Fixture = Fixture::new(
+        r#"
+        [[include]]
+        match = ["a/A"]
+        bind = true
+        depth = 2
+
+        [[include]]
+        match = ["d/D"]
+        bind = false
+        "#,
+        [
+            ClassBuilder::class("a/A")
+                .signature("Ljava/lang/Object;Ljava/lang/Iterable<Lf/F;>;")
+                .method("b", "()Lb/B;")
+                .generic_method(
+                    "g",
+                    "()Ljava/util/Optional;",
+                    "()Ljava/util/Optional<Lg/G;>;",
+                )
+                .generic_field("h", "Ljava/util/List;", "Ljava/util/List<Lh/H;>;"),
+            ClassBuilder::class("b/B").method("c", "(Lc/C;)V"),
+            ClassBuilder::class("c/C").extends("d/D").implements("e/E"),
+            ClassBuilder::class("d/D"),
+            ClassBuilder::interface("e/E"),
+            ClassBuilder::class("f/F"),
+            ClassBuilder::class("g/G"),
+            ClassBuilder::class("h/H"),
+        ],
+    );
+    let classes: Vec<JavaClass> = fixture.classes();
+    let mut config: Config = fixture.config;
+    include_referenced(
+        &mut config,
+        &classes.iter().collect::<Vec<_>>(),
+        &HashMap::new(),
+    );
+
+    // `c/C` is two references away from `a/A`, so its own references are out of reach
+    let bound = |path: &str| config.resolve_class(path).bind;
+    assert!(bound("b/B") && bound("c/C"));
+    assert!(!bound("d/D") && !bound("e/E"));
+    // Type arguments of generic signatures are references too
+    assert!(bound("f/F") && bound("g/G") && bound("h/H"));
+    assert_eq!(config.rules.len(), 7);
+
+    config.rules[0].depth = Some(3);
+    include_referenced(
+        &mut config,
+        &classes.iter().collect::<Vec<_>>(),
+        &HashMap::new(),
+    );
+    let bound = |path: &str| config.resolve_class(path).bind;
+    assert!(bound("e/E"));
+    assert!(!bound("d/D"), "excluded classes stay unbound");
+}
@@ -1,9 +1,10 @@
 use super::Difference;
 use crate::{io_data_err, io_data_error, prelude::*, pretty_path};
 use std::{
+    collections::HashSet,
     fs::{self, *},
     io::{self, BufRead, BufReader, Cursor, ErrorKind},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 const MARKER_COMMENT: &str =
@@ -25,6 +26,7 @@ pub fn write_generated(path: &impl AsRef<Path>, contents: &[u8]) -> io::Result<(
         Some("java") => "// ",
         Some("py") => "# ",
         Some("sh") => "# ",
+        Some("toml") => "# ",
         _ => "// ", // Default to // for unknown extensions
     };
 
@@ -46,16 +48,7 @@ pub fn write_generated(path: &impl AsRef<Path>, contents: &[u8]) -> io::Result<(
                 },
             )?;
 
-            let mut found_marker: bool = false;
-            for prefix in ["// ", "# "] {
-                if first_line.starts_with(prefix) && (&first_line[prefix.len()..] == MARKER_COMMENT)
-                {
-                    found_marker = true;
-                    break;
-                }
-            }
-
-            if !found_marker {
+            if !is_marker(&first_line) {
                 return io_data_err!(
                     "Cannot overwrite {:?}:  File exists, and first line {:?} doesn't match expected MARKER_COMMENT {:?}",
                     pretty_path!(path),
@@ -86,6 +79,44 @@ pub fn write_generated(path: &impl AsRef<Path>, contents: &[u8]) -> io::Result<(
         .map_err(|e: io::Error| io_data_error!("Failed to write to file {:?}:\n{}", path, e))
 }
 
+/// Whether the file at `path` starts with the marker comment of [`write_generated`].
+pub fn is_generated(path: &impl AsRef<Path>) -> io::Result<bool> {
+    let mut first_line: String = String::new();
+    match read_line_no_eol(&mut BufReader::new(File::open(path)?), &mut first_line) {
+        Ok(_) => Ok(is_marker(&first_line)),
+        Err(e) if e.kind() == ErrorKind::InvalidData => Ok(false), // Not even text
+        Err(e) => Err(e),
+    }
+}
+
+/// Deletes the generated files under `dir` that aren't in `keep`, then the directories this leaves empty.
+///
+/// Files without the marker comment of [`write_generated`] are never touched. Returns whether anything was deleted.
+pub fn remove_stale_generated(dir: &Path, keep: &HashSet<PathBuf>) -> io::Result<bool> {
+    let mut removed: bool = false;
+    for entry in read_dir(dir)? {
+        let path: PathBuf = entry?.path();
+        if path.is_dir() {
+            if remove_stale_generated(&path, keep)? && read_dir(&path)?.next().is_none() {
+                info!("REMOVED: {:?}", pretty_path!(path));
+                remove_dir(&path)?;
+                removed = true;
+            }
+        } else if !keep.contains(&path) && is_generated(&path)? {
+            info!("REMOVED: {:?}", pretty_path!(path));
+            remove_file(&path)?;
+            removed = true;
+        }
+    }
+    Ok(removed)
+}
+
+fn is_marker(line: &str) -> bool {
+    ["// ", "# "]
+        .iter()
+        .any(|prefix: &&str| line.strip_prefix(prefix) == Some(MARKER_COMMENT))
+}
+
 fn read_line_no_eol(reader: &mut impl BufRead, buffer: &mut String) -> io::Result<usize> {
     let size: usize = reader.read_line(buffer)?;
     // FIXME: Use `buffer = buffer.trim_end().to_string()` instead?
@@ -94,3 +125,28 @@ fn read_line_no_eol(reader: &mut impl BufRead, buffer: &mut String) -> io::Resul
     }
     Ok(size)
 }
+
+#[test]
+fn remove_stale_generated_test() {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("java-oxide-stale-{}", std::process::id()));
+    let _ = remove_dir_all(&dir);
+    let kept: PathBuf = dir.join("lib.rs");
+    let stale: PathBuf = dir.join("old/mod.rs");
+    let custom: PathBuf = dir.join("custom/mod.rs");
+    write_generated(&kept, b"").unwrap();
+    write_generated(&stale, b"").unwrap();
+    create_dir_all(custom.parent().unwrap()).unwrap();
+    fs::write(&custom, "// written by hand\n").unwrap();
+
+    let keep: HashSet<PathBuf> = HashSet::from([kept.clone()]);
+    assert!(remove_stale_generated(&dir, &keep).unwrap());
+    assert!(kept.exists());
+    assert!(custom.exists());
+    assert!(!stale.exists());
+    assert!(!dir.join("old").exists());
+
+    // Nothing left to remove
+    assert!(!remove_stale_generated(&dir, &keep).unwrap());
+    remove_dir_all(&dir).unwrap();
+}
@@ -2,4 +2,4 @@ mod difference;
 mod generated_file;
 
 pub use difference::Difference;
-pub use generated_file::write_generated;
+pub use generated_file::{is_generated, remove_stale_generated, write_generated};
@@ -10,9 +10,6 @@ set "RUST_BACKTRACE=1"
 ..\target\debug\java-oxide-gen.exe -vv generate >jog.txt 2>&1
 if %errorlevel% neq 0 exit /b %errorlevel%
 
-cargo fmt -- --config-path ..\rustfmt.toml --config "max_width=200"
-if %errorlevel% neq 0 exit /b %errorlevel%
-
 cargo check >cargo-check-output.txt 2>&1
 if %errorlevel% neq 0 exit /b %errorlevel%
 